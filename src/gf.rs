@@ -1,3 +1,4 @@
+use num::{One, Zero};
 use std::convert::{From, Into, TryInto};
 use std::fmt::Display;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
@@ -160,6 +161,22 @@ impl<T: TryInto<i64>, const P: u64> From<T> for GF<P> {
     }
 }
 
+impl<const P: u64> Zero for GF<P> {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const P: u64> One for GF<P> {
+    fn one() -> Self {
+        Self(1 % P)
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -0,0 +1,270 @@
+use super::{Graph, WGraph};
+use num::Zero;
+use std::ops::Add;
+
+/// Centroid decomposition of a tree
+///
+/// Each node is the centroid of exactly one component,
+/// and the components form the centroid tree of depth O(log n).
+///
+/// ```
+/// # use competitive::graph::make_undirected_graph;
+/// # use competitive::graph::centroid::CentroidDecomposition;
+/// // path 0 - 1 - 2 - 3 - 4
+/// let g = make_undirected_graph(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+/// let cd = CentroidDecomposition::new(&g);
+/// assert_eq!(cd.root(), 2);
+/// assert_eq!(cd.parent(0), Some(1));
+/// assert_eq!(cd.depth(0), 2);
+/// assert_eq!(cd.component(3), &[3, 4]);
+///
+/// // branches of the component of 2, with distances from 2
+/// let b = cd.visit(&g, 2);
+/// assert_eq!(b, vec![vec![(1, 1), (0, 2)], vec![(3, 1), (4, 2)]]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CentroidDecomposition {
+    root: usize,
+    parent: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    // component[c]: nodes of the component whose centroid is c, starting with c
+    component: Vec<Vec<usize>>,
+}
+
+impl CentroidDecomposition {
+    /// O(n log n).
+    /// Decompose an undirected tree `g`.
+    pub fn new<'a, G: Graph<'a, NodeId = usize>>(g: &'a G) -> Self {
+        let n = g.len();
+        let mut parent = vec![None; n];
+        let mut depth = vec![usize::MAX; n];
+        let mut component = vec![vec![]; n];
+        let mut root = 0;
+
+        // temporary buffers for each component
+        let mut par = vec![usize::MAX; n];
+        let mut size = vec![0; n];
+
+        let mut stack = if n > 0 { vec![(0, None, 0)] } else { vec![] };
+        while let Some((s, p, d)) = stack.pop() {
+            // nodes of the component in BFS order
+            let mut nodes = vec![s];
+            par[s] = usize::MAX;
+            let mut i = 0;
+            while i < nodes.len() {
+                let u = nodes[i];
+                for v in g.neighbors(u) {
+                    if v != par[u] && depth[v] == usize::MAX {
+                        par[v] = u;
+                        nodes.push(v);
+                    }
+                }
+                i += 1;
+            }
+
+            for &u in nodes.iter().rev() {
+                size[u] = 1;
+                for v in g.neighbors(u) {
+                    if v != par[u] && depth[v] == usize::MAX {
+                        size[u] += size[v];
+                    }
+                }
+            }
+
+            // walk toward the heavy side until no subtree exceeds half
+            let total = nodes.len();
+            let mut c = s;
+            loop {
+                let heavy = g
+                    .neighbors(c)
+                    .find(|&v| v != par[c] && depth[v] == usize::MAX && size[v] * 2 > total);
+                match heavy {
+                    Some(v) => c = v,
+                    None => break,
+                }
+            }
+
+            parent[c] = p;
+            depth[c] = d;
+            if p.is_none() {
+                root = c;
+            }
+            let k = nodes.iter().position(|&u| u == c).unwrap();
+            nodes.swap(0, k);
+            component[c] = nodes;
+
+            for v in g.neighbors(c) {
+                if depth[v] == usize::MAX {
+                    stack.push((v, Some(c), d + 1));
+                }
+            }
+        }
+
+        Self {
+            root,
+            parent,
+            depth,
+            component,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Root of the centroid tree, i.e. the centroid of the whole tree
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Parent of `c` in the centroid tree
+    pub fn parent(&self, c: usize) -> Option<usize> {
+        self.parent[c]
+    }
+
+    /// Depth of `c` in the centroid tree
+    pub fn depth(&self, c: usize) -> usize {
+        self.depth[c]
+    }
+
+    /// Nodes of the component whose centroid is `c`, starting with `c`.
+    /// This is the subtree of `c` in the centroid tree.
+    pub fn component(&self, c: usize) -> &[usize] {
+        &self.component[c]
+    }
+
+    /// O(size of the component).
+    /// Nodes of the component of `c` other than `c`, grouped by the branches from `c`.
+    /// Each node is paired with the distance from `c`, and nodes of each branch are in BFS order.
+    pub fn visit<'a, G: Graph<'a, NodeId = usize>>(
+        &self,
+        g: &'a G,
+        c: usize,
+    ) -> Vec<Vec<(usize, usize)>> {
+        self.branches(c, |u| g.neighbors(u).map(|v| (v, 1)).collect(), 0)
+    }
+
+    /// O(size of the component).
+    /// Weighted version of `visit`.
+    pub fn visit_weighted<'a, G>(&self, g: &'a G, c: usize) -> Vec<Vec<(usize, G::Weight)>>
+    where
+        G: WGraph<'a, NodeId = usize>,
+        G::Weight: Clone + Zero + Add<Output = G::Weight>,
+    {
+        self.branches(c, |u| g.weighted_neighbors(u).collect(), G::Weight::zero())
+    }
+
+    fn branches<W: Clone + Add<Output = W>>(
+        &self,
+        c: usize,
+        adj: impl Fn(usize) -> Vec<(usize, W)>,
+        zero: W,
+    ) -> Vec<Vec<(usize, W)>> {
+        let d = self.depth[c];
+        let inside = |v: usize| self.depth[v] > d;
+
+        let mut ret = vec![];
+        for (v, w) in adj(c) {
+            if !inside(v) {
+                continue;
+            }
+            // (node, parent, distance)
+            let mut nodes = vec![(v, c, zero.clone() + w)];
+            let mut i = 0;
+            while i < nodes.len() {
+                let (u, p, du) = nodes[i].clone();
+                for (x, w) in adj(u) {
+                    if x != p && inside(x) {
+                        nodes.push((x, u, du.clone() + w));
+                    }
+                }
+                i += 1;
+            }
+            ret.push(nodes.into_iter().map(|(u, _, d)| (u, d)).collect());
+        }
+        ret
+    }
+}
+
+#[test]
+fn centroid_decomposition_test() {
+    use super::{make_dist_table, make_undirected_graph, make_weighted_undirected_graph};
+
+    for seed in 0..20 {
+        let n = 1 + seed * 5;
+        let edges = (1..n)
+            .map(|v| ((v * 7 + seed) % v, v, ((v * 13 + seed) % 5) as u64))
+            .collect::<Vec<_>>();
+        let g = make_undirected_graph(
+            n,
+            &edges.iter().map(|&(u, v, _)| (u, v)).collect::<Vec<_>>(),
+        );
+        let wg = make_weighted_undirected_graph(n, &edges);
+        let cd = CentroidDecomposition::new(&g);
+
+        // structure of the centroid tree
+        let mut log = 0;
+        while 1 << log <= n {
+            log += 1;
+        }
+        assert_eq!(cd.component(cd.root()).len(), n);
+        for c in 0..n {
+            assert!(cd.depth(c) < log);
+            assert_eq!(cd.component(c)[0], c);
+            match cd.parent(c) {
+                Some(p) => {
+                    assert_eq!(cd.depth(c), cd.depth(p) + 1);
+                    assert!(cd.component(c).len() * 2 <= cd.component(p).len());
+                    assert!(cd.component(c).iter().all(|v| cd.component(p).contains(v)));
+                }
+                None => assert_eq!(c, cd.root()),
+            }
+        }
+
+        // count pairs of nodes by distance, combining branches of each centroid
+        let mut cnt = vec![0_usize; n];
+        let mut wsum = 0_u64;
+        for c in 0..n {
+            let mut all = vec![0_usize; n];
+            all[0] = 1;
+            for b in cd.visit(&g, c) {
+                for &(_, d) in &b {
+                    for (e, &k) in all.iter().enumerate() {
+                        if d + e < n {
+                            cnt[d + e] += k;
+                        }
+                    }
+                }
+                for &(_, d) in &b {
+                    all[d] += 1;
+                }
+            }
+            for b in cd.visit_weighted(&wg, c) {
+                wsum += b.iter().map(|&(_, d)| d).sum::<u64>();
+            }
+        }
+
+        let mut expected = vec![0_usize; n];
+        let mut wexpected = 0_u64;
+        for u in 0..n {
+            for (v, d) in make_dist_table(&g, u).into_iter().enumerate() {
+                if u < v {
+                    expected[d.unwrap()] += 1;
+                }
+            }
+            // distances from each centroid to the nodes of its component
+            let c = u;
+            let dist = super::shortest_path::dijkstra(&wg, c).dist;
+            wexpected += cd.component(c)[1..]
+                .iter()
+                .map(|&v| dist[v].unwrap())
+                .sum::<u64>();
+        }
+        assert_eq!(cnt[1..], expected[1..]);
+        assert_eq!(wsum, wexpected);
+    }
+}
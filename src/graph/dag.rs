@@ -0,0 +1,328 @@
+use super::shortest_path::ShortestPaths;
+use super::{Graph, WGraph};
+use crate::inf::MaybeInf::*;
+use num::{One, Zero};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::ops::Add;
+
+fn in_degrees<'a, G: Graph<'a, NodeId = usize>>(g: &'a G) -> Vec<usize> {
+    let mut ret = vec![0; g.len()];
+    for u in 0..g.len() {
+        for v in g.neighbors(u) {
+            ret[v] += 1;
+        }
+    }
+    ret
+}
+
+/// O(V + E).
+/// Topological order of nodes by Kahn's algorithm.
+///
+/// If the graph has a cycle, returns `Err` with nodes `[v_0, v_1, ..., v_{k-1}]` of a cycle
+/// such that `v_i -> v_{i+1}` and `v_{k-1} -> v_0` are edges.
+///
+/// ```
+/// # use competitive::graph::make_directed_graph;
+/// # use competitive::graph::dag::topological_sort;
+/// let g = make_directed_graph(3, &[(2, 0), (0, 1)]);
+/// assert_eq!(topological_sort(&g), Ok(vec![2, 0, 1]));
+///
+/// let g = make_directed_graph(4, &[(0, 1), (1, 2), (2, 3), (3, 1)]);
+/// assert_eq!(topological_sort(&g), Err(vec![1, 2, 3]));
+/// ```
+pub fn topological_sort<'a, G: Graph<'a, NodeId = usize>>(
+    g: &'a G,
+) -> Result<Vec<usize>, Vec<usize>> {
+    let n = g.len();
+    let mut deg = in_degrees(g);
+    let mut q = (0..n).filter(|&v| deg[v] == 0).collect::<VecDeque<_>>();
+    let mut ret = Vec::with_capacity(n);
+
+    while let Some(u) = q.pop_front() {
+        ret.push(u);
+        for v in g.neighbors(u) {
+            deg[v] -= 1;
+            if deg[v] == 0 {
+                q.push_back(v);
+            }
+        }
+    }
+
+    if ret.len() == n {
+        return Ok(ret);
+    }
+
+    // every remaining node has a remaining predecessor, so walking backward finds a cycle
+    let mut pred = vec![usize::MAX; n];
+    for u in (0..n).filter(|&u| deg[u] > 0) {
+        for v in g.neighbors(u) {
+            if deg[v] > 0 {
+                pred[v] = u;
+            }
+        }
+    }
+
+    let mut visited = vec![false; n];
+    let mut v = (0..n).find(|&v| deg[v] > 0).unwrap();
+    while !visited[v] {
+        visited[v] = true;
+        v = pred[v];
+    }
+
+    let mut cycle = vec![v];
+    let mut u = pred[v];
+    while u != v {
+        cycle.push(u);
+        u = pred[u];
+    }
+    cycle.reverse();
+    let k = cycle.iter().enumerate().min_by_key(|e| e.1).unwrap().0;
+    cycle.rotate_left(k);
+    Err(cycle)
+}
+
+/// O(V log V + E).
+/// Lexicographically smallest topological order, or `None` if the graph has a cycle.
+pub fn lex_min_topological_sort<'a, G: Graph<'a, NodeId = usize>>(g: &'a G) -> Option<Vec<usize>> {
+    let n = g.len();
+    let mut deg = in_degrees(g);
+    let mut q = (0..n)
+        .filter(|&v| deg[v] == 0)
+        .map(Reverse)
+        .collect::<BinaryHeap<_>>();
+    let mut ret = Vec::with_capacity(n);
+
+    while let Some(Reverse(u)) = q.pop() {
+        ret.push(u);
+        for v in g.neighbors(u) {
+            deg[v] -= 1;
+            if deg[v] == 0 {
+                q.push(Reverse(v));
+            }
+        }
+    }
+
+    if ret.len() == n {
+        Some(ret)
+    } else {
+        None
+    }
+}
+
+/// O(2^V V + E).
+/// Number of topological orders by bitmask DP. Returns zero if the graph has a cycle.
+pub fn count_topological_orders<'a, G, T>(g: &'a G) -> T
+where
+    G: Graph<'a, NodeId = usize>,
+    T: Clone + Zero + One,
+{
+    let n = g.len();
+    assert!(n < 32);
+
+    // pred[v]: set of nodes which must come before v
+    let mut pred = vec![0_usize; n];
+    for u in 0..n {
+        for v in g.neighbors(u) {
+            pred[v] |= 1 << u;
+        }
+    }
+
+    let mut dp = vec![T::zero(); 1 << n];
+    dp[0] = T::one();
+    for s in 0..(1_usize << n) {
+        if dp[s].is_zero() {
+            continue;
+        }
+        for (v, &p) in pred.iter().enumerate() {
+            if s >> v & 1 == 0 && p & s == p {
+                dp[s | 1 << v] = dp[s | 1 << v].clone() + dp[s].clone();
+            }
+        }
+    }
+    dp[(1 << n) - 1].clone()
+}
+
+fn dag_path<'a, G>(
+    g: &'a G,
+    start: usize,
+    better: impl Fn(&G::Weight, &G::Weight) -> bool,
+) -> ShortestPaths<G::Weight>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Zero + Add<Output = G::Weight>,
+{
+    let n = g.len();
+    let ord = topological_sort(g).expect("graph must be a DAG");
+    let mut dist = vec![None; n];
+    let mut prev = vec![None; n];
+    dist[start] = Some(G::Weight::zero());
+
+    for u in ord {
+        let du = match dist[u].clone() {
+            Some(d) => d,
+            None => continue,
+        };
+        for (v, w) in g.weighted_neighbors(u) {
+            let nd = du.clone() + w;
+            if dist[v].as_ref().is_none_or(|dv| better(&nd, dv)) {
+                dist[v] = Some(nd);
+                prev[v] = Some(u);
+            }
+        }
+    }
+
+    ShortestPaths {
+        dist: dist.into_iter().map(|d| d.map_or(Inf, NonInf)).collect(),
+        prev,
+    }
+}
+
+/// O(V + E).
+/// Shortest paths from `start` on a DAG. Weights can be negative.
+///
+/// Panics if the graph has a cycle.
+pub fn dag_shortest_path<'a, G>(g: &'a G, start: usize) -> ShortestPaths<G::Weight>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Ord + Zero + Add<Output = G::Weight>,
+{
+    dag_path(g, start, |a, b| a < b)
+}
+
+/// O(V + E).
+/// Longest paths from `start` on a DAG. Weights can be negative.
+/// `dist[v]` is the maximum total weight over paths from `start` to `v`.
+/// Nodes unreachable from `start` have `Inf` as their distance and `None` as their path.
+///
+/// Panics if the graph has a cycle.
+///
+/// ```
+/// # use competitive::graph::make_weighted_directed_graph;
+/// # use competitive::graph::dag::dag_longest_path;
+/// # use competitive::inf::MaybeInf::*;
+/// let g = make_weighted_directed_graph(4, &[(0, 1, 1), (0, 2, 5), (1, 2, 2), (2, 3, 1)]);
+/// let lp = dag_longest_path(&g, 1);
+/// assert_eq!(lp.dist, vec![Inf, NonInf(0), NonInf(2), NonInf(3)]);
+/// assert_eq!(dag_longest_path(&g, 0).path_to(3), Some(vec![0, 2, 3]));
+/// ```
+pub fn dag_longest_path<'a, G>(g: &'a G, start: usize) -> ShortestPaths<G::Weight>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Ord + Zero + Add<Output = G::Weight>,
+{
+    dag_path(g, start, |a, b| a > b)
+}
+
+#[test]
+fn topological_sort_test() {
+    use super::make_directed_graph;
+    use itertools::Itertools;
+
+    for seed in 0..100 {
+        let n = 7;
+        let edges = (0..n + seed % 5)
+            .map(|i| ((i * 3 + seed) % n, (i * 5 + seed * 7 + 1) % n))
+            .filter(|&(u, v)| u != v)
+            .map(|(u, v)| {
+                if seed % 4 == 0 {
+                    (u, v)
+                } else {
+                    (u.min(v), u.max(v))
+                }
+            })
+            .collect::<Vec<_>>();
+        let g = make_directed_graph(n, &edges);
+
+        let is_order = |ord: &[usize]| {
+            let mut pos = vec![0; n];
+            for (i, &v) in ord.iter().enumerate() {
+                pos[v] = i;
+            }
+            edges.iter().all(|&(u, v)| pos[u] < pos[v])
+        };
+        let orders = (0..n)
+            .permutations(n)
+            .filter(|p| is_order(p))
+            .collect::<Vec<_>>();
+
+        match topological_sort(&g) {
+            Ok(ord) => {
+                assert!(is_order(&ord));
+                assert_eq!(lex_min_topological_sort(&g), orders.first().cloned());
+            }
+            Err(cycle) => {
+                assert!(orders.is_empty());
+                assert!(!cycle.is_empty());
+                for i in 0..cycle.len() {
+                    let e = (cycle[i], cycle[(i + 1) % cycle.len()]);
+                    assert!(edges.contains(&e));
+                }
+                assert_eq!(lex_min_topological_sort(&g), None);
+            }
+        }
+        assert_eq!(count_topological_orders::<_, u64>(&g), orders.len() as u64);
+    }
+
+    // self loop
+    let g = make_directed_graph(2, &[(0, 1), (1, 1)]);
+    assert_eq!(topological_sort(&g), Err(vec![1]));
+
+    let g = make_directed_graph(20, &[]);
+    assert_eq!(
+        count_topological_orders::<_, u64>(&g),
+        (1..=20).product::<u64>()
+    );
+}
+
+#[test]
+fn dag_path_test() {
+    use super::make_weighted_directed_graph;
+    use super::shortest_path::bellman_ford;
+
+    for seed in 0..50 {
+        let n = 12;
+        let edges = (0..n * 2)
+            .map(|i| {
+                let (u, v) = ((i * 5 + seed) % n, (i * 7 + seed * 3 + 1) % n);
+                (u.min(v), u.max(v), ((i * 31 + seed) % 17) as i64 - 5)
+            })
+            .filter(|&(u, v, _)| u != v)
+            .collect::<Vec<_>>();
+        let g = make_weighted_directed_graph(n, &edges);
+        let neg = edges
+            .iter()
+            .map(|&(u, v, w)| (u, v, -w))
+            .collect::<Vec<_>>();
+        let gn = make_weighted_directed_graph(n, &neg);
+
+        for s in 0..n {
+            let sp = dag_shortest_path(&g, s);
+            assert_eq!(sp.dist, bellman_ford(&g, s));
+            let lp = dag_longest_path(&g, s);
+            let expected = bellman_ford(&gn, s)
+                .into_iter()
+                .map(|d| if d == Inf { Inf } else { -d })
+                .collect::<Vec<_>>();
+            assert_eq!(lp.dist, expected);
+
+            for (res, sign) in [(sp, 1), (lp, -1)] {
+                for v in 0..n {
+                    if let Some(path) = res.path_to(v) {
+                        let w = path
+                            .windows(2)
+                            .map(|e| {
+                                let ws = edges
+                                    .iter()
+                                    .filter(|&&(a, b, _)| (a, b) == (e[0], e[1]))
+                                    .map(|&(_, _, w)| w * sign);
+                                ws.min().unwrap() * sign
+                            })
+                            .sum::<i64>();
+                        assert_eq!(res.dist[v], NonInf(w));
+                    }
+                }
+            }
+        }
+    }
+}
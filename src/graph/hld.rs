@@ -0,0 +1,291 @@
+use super::Graph;
+use crate::monoid::Monoid;
+use crate::segment_tree::SegmentTree;
+use std::ops::Range;
+
+/// Heavy-light decomposition
+///
+/// Nodes are numbered in pre-order visiting heavy children first,
+/// so that each heavy path and each subtree occupy contiguous positions.
+/// Put the value of node `v` at position `pos(v)` of a sequence (e.g. `SegmentTree`).
+/// For values on edges, put the value of the edge between `v` and its parent at `pos(v)` and use `edge = true`.
+///
+/// ```
+/// # use competitive::graph::make_undirected_graph;
+/// # use competitive::graph::hld::Hld;
+/// # use competitive::monoid::Sum;
+/// # use competitive::segment_tree::SegmentTree;
+/// let g = make_undirected_graph(5, &[(0, 1), (0, 2), (1, 3), (1, 4)]);
+/// let hld = Hld::new(&g, 0);
+/// let mut st = SegmentTree::<Sum<i64>>::new(5);
+/// for v in 0..5 {
+///     st.set(hld.pos(v), v as i64 * 10);
+/// }
+/// assert_eq!(hld.path_query(&st, 3, 2, false).0, 30 + 10 + 0 + 20);
+/// assert_eq!(hld.path_query(&st, 3, 2, true).0, 30 + 10 + 20);
+/// assert_eq!(hld.subtree_query(&st, 1, false).0, 10 + 30 + 40);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Hld {
+    parent: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    head: Vec<usize>,
+    pos: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl Hld {
+    /// O(n).
+    /// Construct from an undirected tree `g` rooted at `root`.
+    pub fn new<'a, G: Graph<'a, NodeId = usize>>(g: &'a G, root: usize) -> Self {
+        let n = g.len();
+
+        // parent, depth and children in DFS order, then subtree sizes in reverse order
+        let mut parent = vec![None; n];
+        let mut depth = vec![0; n];
+        let mut children = vec![vec![]; n];
+        let mut order = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        let mut stack = vec![root];
+        visited[root] = true;
+        while let Some(u) = stack.pop() {
+            order.push(u);
+            for v in g.neighbors(u) {
+                if !visited[v] {
+                    visited[v] = true;
+                    parent[v] = Some(u);
+                    depth[v] = depth[u] + 1;
+                    children[u].push(v);
+                    stack.push(v);
+                }
+            }
+        }
+        assert_eq!(order.len(), n, "graph is not connected");
+        let mut size = vec![1; n];
+        for &v in order.iter().rev() {
+            if let Some(p) = parent[v] {
+                size[p] += size[v];
+            }
+        }
+
+        let mut head = vec![root; n];
+        let mut pos = vec![0; n];
+        let mut cur = 0;
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            pos[v] = cur;
+            cur += 1;
+
+            let heavy = children[v].iter().cloned().max_by_key(|&c| size[c]);
+            for &c in children[v].iter().rev() {
+                if Some(c) != heavy {
+                    head[c] = c;
+                    stack.push(c);
+                }
+            }
+            if let Some(h) = heavy {
+                head[h] = head[v];
+                stack.push(h);
+            }
+        }
+
+        Self {
+            parent,
+            depth,
+            head,
+            pos,
+            size,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos.is_empty()
+    }
+
+    /// Position of `v` in HLD order
+    pub fn pos(&self, v: usize) -> usize {
+        self.pos[v]
+    }
+
+    /// Range of positions of the subtree of `v`
+    pub fn subtree(&self, v: usize, edge: bool) -> Range<usize> {
+        self.pos[v] + edge as usize..self.pos[v] + self.size[v]
+    }
+
+    /// O(log n).
+    /// Lowest common ancestor
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = (u, v);
+        while self.head[u] != self.head[v] {
+            if self.pos[self.head[u]] > self.pos[self.head[v]] {
+                u = self.parent[self.head[u]].unwrap();
+            } else {
+                v = self.parent[self.head[v]].unwrap();
+            }
+        }
+        if self.depth[u] < self.depth[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    /// O(log n).
+    /// Decompose the path from `u` to `v` into ranges of positions in order from `u` to `v`.
+    /// Each range is paired with a flag that is true if the path goes through it in decreasing order of positions.
+    ///
+    /// If `edge` is true, the lowest common ancestor is excluded.
+    pub fn path_directed(&self, u: usize, v: usize, edge: bool) -> Vec<(Range<usize>, bool)> {
+        let (mut u, mut v) = (u, v);
+        let mut up = vec![];
+        let mut down = vec![];
+        while self.head[u] != self.head[v] {
+            if self.pos[self.head[u]] > self.pos[self.head[v]] {
+                up.push((self.pos[self.head[u]]..self.pos[u] + 1, true));
+                u = self.parent[self.head[u]].unwrap();
+            } else {
+                down.push((self.pos[self.head[v]]..self.pos[v] + 1, false));
+                v = self.parent[self.head[v]].unwrap();
+            }
+        }
+        if self.pos[u] >= self.pos[v] {
+            up.push((self.pos[v] + edge as usize..self.pos[u] + 1, true));
+        } else {
+            down.push((self.pos[u] + edge as usize..self.pos[v] + 1, false));
+        }
+
+        up.extend(down.into_iter().rev());
+        up.retain(|(r, _)| !r.is_empty());
+        up
+    }
+
+    /// O(log n).
+    /// Decompose the path between `u` and `v` into ranges of positions.
+    ///
+    /// If `edge` is true, the lowest common ancestor is excluded.
+    pub fn path(&self, u: usize, v: usize, edge: bool) -> Vec<Range<usize>> {
+        self.path_directed(u, v, edge)
+            .into_iter()
+            .map(|(r, _)| r)
+            .collect()
+    }
+
+    /// O(log^2 n).
+    /// Fold values on the path between `u` and `v`. `T` must be commutative.
+    pub fn path_query<T: Clone + Monoid>(
+        &self,
+        st: &SegmentTree<T>,
+        u: usize,
+        v: usize,
+        edge: bool,
+    ) -> T {
+        self.path(u, v, edge)
+            .into_iter()
+            .fold(T::mempty(), |acc, r| T::mappend(&acc, &st.query(r)))
+    }
+
+    /// O(log^2 n).
+    /// Fold values on the path from `u` to `v` in order.
+    ///
+    /// `rev` must hold the same values as `st` in reversed order,
+    /// i.e. the value at position `i` of `st` is at position `n - 1 - i` of `rev`.
+    pub fn path_query_directed<T: Clone + Monoid>(
+        &self,
+        st: &SegmentTree<T>,
+        rev: &SegmentTree<T>,
+        u: usize,
+        v: usize,
+        edge: bool,
+    ) -> T {
+        let n = self.len();
+        self.path_directed(u, v, edge)
+            .into_iter()
+            .fold(T::mempty(), |acc, (r, reversed)| {
+                let x = if reversed {
+                    rev.query(n - r.end..n - r.start)
+                } else {
+                    st.query(r)
+                };
+                T::mappend(&acc, &x)
+            })
+    }
+
+    /// O(log n).
+    /// Fold values in the subtree of `v`. `T` must be commutative.
+    pub fn subtree_query<T: Clone + Monoid>(&self, st: &SegmentTree<T>, v: usize, edge: bool) -> T {
+        st.query(self.subtree(v, edge))
+    }
+}
+
+#[test]
+fn hld_test() {
+    use super::make_undirected_graph;
+    use super::tree::RootedTree;
+    use crate::monoid::Sum;
+
+    // sequence of nodes, which is not commutative
+    #[derive(Clone, Debug, PartialEq)]
+    struct Seq(Vec<usize>);
+    impl Monoid for Seq {
+        fn mempty() -> Self {
+            Seq(vec![])
+        }
+        fn mappend(l: &Self, r: &Self) -> Self {
+            Seq(l.0.iter().chain(r.0.iter()).cloned().collect())
+        }
+    }
+
+    for seed in 0..20 {
+        let n = 1 + seed * 4;
+        let par = (0..n)
+            .map(|v| if v == 0 { 0 } else { (v * 5 + seed) % v })
+            .collect::<Vec<_>>();
+        let edges = (1..n).map(|v| (par[v], v)).collect::<Vec<_>>();
+        let g = make_undirected_graph(n, &edges);
+        let root = seed % n;
+        let t = RootedTree::new(&g, root);
+        let hld = Hld::new(&g, root);
+
+        let mut st = SegmentTree::<Seq>::new(n);
+        let mut rev = SegmentTree::<Seq>::new(n);
+        let mut sum = SegmentTree::<Sum<usize>>::new(n);
+        for v in 0..n {
+            st.set(hld.pos(v), Seq(vec![v]));
+            rev.set(n - 1 - hld.pos(v), Seq(vec![v]));
+            sum.set(hld.pos(v), v);
+        }
+
+        for u in 0..n {
+            assert_eq!(hld.subtree(u, false).len(), t.size(u));
+            let sub = (0..n).filter(|&v| t.is_ancestor(u, v)).sum::<usize>();
+            assert_eq!(hld.subtree_query(&sum, u, false).0, sub);
+            assert_eq!(hld.subtree_query(&sum, u, true).0, sub - u);
+
+            for v in 0..n {
+                let path = t.path(u, v);
+                let w = t.lca(u, v);
+                assert_eq!(hld.lca(u, v), w);
+                assert!(
+                    hld.path(u, v, false).len() <= 2 * (usize::BITS - n.leading_zeros()) as usize
+                );
+
+                assert_eq!(hld.path_query_directed(&st, &rev, u, v, false).0, path);
+                let edge_path = path.iter().cloned().filter(|&x| x != w).collect::<Vec<_>>();
+                assert_eq!(hld.path_query_directed(&st, &rev, u, v, true).0, edge_path);
+
+                assert_eq!(
+                    hld.path_query(&sum, u, v, false).0,
+                    path.iter().sum::<usize>()
+                );
+                assert_eq!(
+                    hld.path_query(&sum, u, v, true).0,
+                    edge_path.iter().sum::<usize>()
+                );
+            }
+        }
+    }
+}
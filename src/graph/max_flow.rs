@@ -0,0 +1,337 @@
+use num::PrimInt;
+use std::collections::VecDeque;
+
+/// Edge of a flow network
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge<Cap> {
+    pub from: usize,
+    pub to: usize,
+    pub cap: Cap,
+    pub flow: Cap,
+}
+
+#[derive(Clone, Debug)]
+struct InnerEdge<Cap> {
+    to: usize,
+    rev: usize,
+    cap: Cap,
+}
+
+/// Maximum flow by Dinic's algorithm
+///
+/// `Cap::max_value()` can be used as an infinite capacity.
+/// Residual capacities are updated with saturating arithmetic, so that they never overflow.
+///
+/// ```
+/// # use competitive::graph::max_flow::MaxFlow;
+/// let mut mf = MaxFlow::<i64>::new(4);
+/// let e = mf.add_edge(0, 1, 2);
+/// mf.add_edge(0, 2, 1);
+/// mf.add_edge(1, 2, 1);
+/// mf.add_edge(1, 3, 1);
+/// mf.add_edge(2, 3, i64::MAX);
+/// assert_eq!(mf.flow(0, 3), 3);
+/// assert_eq!(mf.get_edge(e).flow, 2);
+/// assert_eq!(mf.min_cut(0), vec![true, false, false, false]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MaxFlow<Cap> {
+    // pos[i]: (node, index in adjacency list) of the i-th edge
+    pos: Vec<(usize, usize)>,
+    g: Vec<Vec<InnerEdge<Cap>>>,
+}
+
+impl<Cap: PrimInt> MaxFlow<Cap> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            pos: vec![],
+            g: vec![vec![]; n],
+        }
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.g.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.g.is_empty()
+    }
+
+    /// O(1).
+    /// Add an edge from `from` to `to` with capacity `cap`, and return its index.
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: Cap) -> usize {
+        assert!(cap >= Cap::zero());
+        let id = self.pos.len();
+        let from_id = self.g[from].len();
+        let to_id = self.g[to].len() + (from == to) as usize;
+        self.pos.push((from, from_id));
+        self.g[from].push(InnerEdge {
+            to,
+            rev: to_id,
+            cap,
+        });
+        self.g[to].push(InnerEdge {
+            to: from,
+            rev: from_id,
+            cap: Cap::zero(),
+        });
+        id
+    }
+
+    /// O(1).
+    /// Get the `i`-th edge with the current flow.
+    pub fn get_edge(&self, i: usize) -> Edge<Cap> {
+        let (u, k) = self.pos[i];
+        let e = &self.g[u][k];
+        let r = &self.g[e.to][e.rev];
+        Edge {
+            from: u,
+            to: e.to,
+            cap: e.cap.saturating_add(r.cap),
+            flow: r.cap,
+        }
+    }
+
+    /// O(m).
+    /// All edges in the order of addition
+    pub fn edges(&self) -> Vec<Edge<Cap>> {
+        (0..self.pos.len()).map(|i| self.get_edge(i)).collect()
+    }
+
+    /// O(1).
+    /// Change the capacity and the flow of the `i`-th edge. Flows of other edges are not changed.
+    pub fn change_edge(&mut self, i: usize, new_cap: Cap, new_flow: Cap) {
+        assert!(Cap::zero() <= new_flow && new_flow <= new_cap);
+        let (u, k) = self.pos[i];
+        let (to, rev) = (self.g[u][k].to, self.g[u][k].rev);
+        self.g[u][k].cap = new_cap - new_flow;
+        self.g[to][rev].cap = new_flow;
+    }
+
+    /// O(n^2 m).
+    /// Send as much flow as possible from `s` to `t`, and return the amount.
+    /// The result saturates at `Cap::max_value()`.
+    pub fn flow(&mut self, s: usize, t: usize) -> Cap {
+        self.flow_limit(s, t, Cap::max_value())
+    }
+
+    /// O(n^2 m).
+    /// Send flow from `s` to `t` up to `limit`, and return the amount.
+    pub fn flow_limit(&mut self, s: usize, t: usize, limit: Cap) -> Cap {
+        assert_ne!(s, t);
+        let n = self.len();
+        let mut total = Cap::zero();
+        while total < limit {
+            let level = self.levels(s);
+            if level[t] == usize::MAX {
+                break;
+            }
+            let mut iter = vec![0; n];
+            let f = self.blocking_flow(s, t, limit - total, level, &mut iter);
+            if f == Cap::zero() {
+                break;
+            }
+            total = total + f;
+        }
+        total
+    }
+
+    fn levels(&self, s: usize) -> Vec<usize> {
+        let mut level = vec![usize::MAX; self.len()];
+        let mut q = VecDeque::new();
+        level[s] = 0;
+        q.push_back(s);
+        while let Some(u) = q.pop_front() {
+            for e in &self.g[u] {
+                if e.cap > Cap::zero() && level[e.to] == usize::MAX {
+                    level[e.to] = level[u] + 1;
+                    q.push_back(e.to);
+                }
+            }
+        }
+        level
+    }
+
+    fn blocking_flow(
+        &mut self,
+        s: usize,
+        t: usize,
+        limit: Cap,
+        mut level: Vec<usize>,
+        iter: &mut [usize],
+    ) -> Cap {
+        let mut total = Cap::zero();
+        // edges on the current path from s, as (node, index in adjacency list)
+        let mut path: Vec<(usize, usize)> = vec![];
+        let mut v = s;
+
+        while total < limit {
+            if v == t {
+                let f = path
+                    .iter()
+                    .fold(limit - total, |f, &(u, i)| f.min(self.g[u][i].cap));
+                for &(u, i) in &path {
+                    let (to, rev) = (self.g[u][i].to, self.g[u][i].rev);
+                    self.g[u][i].cap = self.g[u][i].cap - f;
+                    self.g[to][rev].cap = self.g[to][rev].cap.saturating_add(f);
+                }
+                total = total + f;
+
+                // retreat to the tail of the first saturated edge
+                match path
+                    .iter()
+                    .position(|&(u, i)| self.g[u][i].cap == Cap::zero())
+                {
+                    Some(k) => {
+                        v = path[k].0;
+                        path.truncate(k);
+                    }
+                    None => break,
+                }
+                continue;
+            }
+
+            while iter[v] < self.g[v].len() {
+                let e = &self.g[v][iter[v]];
+                if e.cap > Cap::zero() && level[e.to] == level[v] + 1 {
+                    break;
+                }
+                iter[v] += 1;
+            }
+
+            if iter[v] < self.g[v].len() {
+                path.push((v, iter[v]));
+                v = self.g[v][iter[v]].to;
+            } else {
+                // no more augmenting path through v
+                level[v] = usize::MAX;
+                match path.pop() {
+                    Some((u, _)) => {
+                        iter[u] += 1;
+                        v = u;
+                    }
+                    None => break,
+                }
+            }
+        }
+        total
+    }
+
+    /// O(n + m).
+    /// Nodes reachable from `s` in the residual network.
+    /// After `flow(s, t)`, this is the source side of a minimum cut.
+    pub fn min_cut(&self, s: usize) -> Vec<bool> {
+        let mut visited = vec![false; self.len()];
+        let mut q = VecDeque::new();
+        visited[s] = true;
+        q.push_back(s);
+        while let Some(u) = q.pop_front() {
+            for e in &self.g[u] {
+                if e.cap > Cap::zero() && !visited[e.to] {
+                    visited[e.to] = true;
+                    q.push_back(e.to);
+                }
+            }
+        }
+        visited
+    }
+}
+
+#[test]
+fn max_flow_test() {
+    // compare with the minimum cut by brute force
+    for seed in 0..100_u64 {
+        let n = 2 + (seed % 7) as usize;
+        let edges = (0..n as u64 * 2 + seed % 5)
+            .map(|i| {
+                let x = seed * 1000 + i * 7919;
+                (
+                    (x % n as u64) as usize,
+                    (x / 7 % n as u64) as usize,
+                    x / 13 % 10,
+                )
+            })
+            .collect::<Vec<_>>();
+        let (s, t) = (0, n - 1);
+
+        let mut mf = MaxFlow::<u64>::new(n);
+        for &(u, v, c) in &edges {
+            mf.add_edge(u, v, c);
+        }
+        let f = mf.flow(s, t);
+
+        let cut_value = |side: &[bool]| {
+            edges
+                .iter()
+                .filter(|&&(u, v, _)| side[u] && !side[v])
+                .map(|&(_, _, c)| c)
+                .sum::<u64>()
+        };
+        let min_cut = (0..1_usize << n)
+            .map(|m| (0..n).map(|i| m >> i & 1 == 1).collect::<Vec<_>>())
+            .filter(|side| side[s] && !side[t])
+            .map(|side| cut_value(&side))
+            .min()
+            .unwrap();
+        assert_eq!(f, min_cut);
+
+        let side = mf.min_cut(s);
+        assert!(side[s] && !side[t]);
+        assert_eq!(cut_value(&side), f);
+
+        // flow conservation and capacity constraints
+        let mut excess = vec![0_i64; n];
+        for (e, &(u, v, c)) in mf.edges().iter().zip(&edges) {
+            assert_eq!((e.from, e.to, e.cap), (u, v, c));
+            assert!(e.flow <= e.cap);
+            excess[u] -= e.flow as i64;
+            excess[v] += e.flow as i64;
+        }
+        for (v, &x) in excess.iter().enumerate() {
+            if v != s && v != t {
+                assert_eq!(x, 0);
+            }
+        }
+        assert_eq!(excess[t], f as i64);
+    }
+
+    // flow_limit and change_edge
+    let mut mf = MaxFlow::<i64>::new(3);
+    let e0 = mf.add_edge(0, 1, 10);
+    let e1 = mf.add_edge(1, 2, 10);
+    assert_eq!(mf.flow_limit(0, 2, 4), 4);
+    assert_eq!(mf.flow(0, 2), 6);
+    mf.change_edge(e0, 15, 10);
+    mf.change_edge(e1, 15, 10);
+    assert_eq!(mf.flow(0, 2), 5);
+    assert_eq!(mf.get_edge(e1).flow, 15);
+
+    // a path of infinite capacities saturates the result
+    let inf = i64::MAX;
+    let mut mf = MaxFlow::<i64>::new(4);
+    mf.add_edge(0, 1, 5);
+    mf.add_edge(0, 2, inf);
+    mf.add_edge(1, 3, inf);
+    mf.add_edge(2, 1, inf);
+    mf.add_edge(2, 3, 3);
+    assert_eq!(mf.flow(0, 3), inf);
+
+    let mut mf = MaxFlow::<u64>::new(4);
+    mf.add_edge(0, 1, u64::MAX);
+    mf.add_edge(1, 2, u64::MAX);
+    mf.add_edge(1, 2, 3);
+    mf.add_edge(2, 3, 7);
+    mf.add_edge(2, 3, 8);
+    assert_eq!(mf.flow(0, 3), 15);
+    assert_eq!(mf.min_cut(0), vec![true, true, true, false]);
+    assert_eq!(mf.get_edge(0).cap, u64::MAX);
+
+    // long path does not overflow the stack
+    let n = 1_000_000;
+    let mut mf = MaxFlow::<u32>::new(n);
+    for i in 0..n - 1 {
+        mf.add_edge(i, i + 1, 1 + i as u32 % 3);
+    }
+    assert_eq!(mf.flow(0, n - 1), 1);
+}
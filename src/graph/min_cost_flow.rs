@@ -0,0 +1,462 @@
+use num::{cast, PrimInt};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Edge of a flow network with cost
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge<Cap, Cost> {
+    pub from: usize,
+    pub to: usize,
+    pub cap: Cap,
+    pub flow: Cap,
+    pub cost: Cost,
+}
+
+#[derive(Clone, Debug)]
+struct InnerEdge<Cap, Cost> {
+    to: usize,
+    rev: usize,
+    cap: Cap,
+    cost: Cost,
+}
+
+/// Minimum cost flow by primal-dual algorithm (successive shortest paths with potentials)
+///
+/// Costs can be negative as long as there is no negative cycle.
+/// Edges with lower bounds of flow are respected only by `flow_with_lower_bounds`.
+/// Until it succeeds, flows of such edges are reported without their lower bounds.
+///
+/// ```
+/// # use competitive::graph::min_cost_flow::MinCostFlow;
+/// let mut mcf = MinCostFlow::<i64, i64>::new(4);
+/// mcf.add_edge(0, 1, 2, 1);
+/// mcf.add_edge(0, 2, 1, 2);
+/// mcf.add_edge(1, 3, 1, 3);
+/// mcf.add_edge(1, 2, 1, 1);
+/// mcf.add_edge(2, 3, 2, 1);
+/// assert_eq!(mcf.clone().flow(0, 3, 1), (1, 3));
+/// assert_eq!(mcf.slope(0, 3), vec![(0, 0), (2, 6), (3, 10)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MinCostFlow<Cap, Cost> {
+    // pos[i]: (node, index in adjacency list) of the i-th edge
+    pos: Vec<(usize, usize)>,
+    g: Vec<Vec<InnerEdge<Cap, Cost>>>,
+    lower: Vec<Cap>,
+    // sum of lower bounds of incoming and outgoing edges of each node
+    lower_in: Vec<Cap>,
+    lower_out: Vec<Cap>,
+    // true if flows of lower bounds are sent by flow_with_lower_bounds
+    lower_applied: bool,
+}
+
+impl<Cap: PrimInt, Cost: PrimInt> MinCostFlow<Cap, Cost> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            pos: vec![],
+            g: vec![vec![]; n],
+            lower: vec![],
+            lower_in: vec![Cap::zero(); n],
+            lower_out: vec![Cap::zero(); n],
+            lower_applied: false,
+        }
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.g.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.g.is_empty()
+    }
+
+    fn push_edge(&mut self, from: usize, to: usize, cap: Cap, cost: Cost) -> usize {
+        assert!(cap >= Cap::zero());
+        let id = self.pos.len();
+        let from_id = self.g[from].len();
+        let to_id = self.g[to].len() + (from == to) as usize;
+        self.pos.push((from, from_id));
+        self.g[from].push(InnerEdge {
+            to,
+            rev: to_id,
+            cap,
+            cost,
+        });
+        self.g[to].push(InnerEdge {
+            to: from,
+            rev: from_id,
+            cap: Cap::zero(),
+            cost: Cost::zero() - cost,
+        });
+        id
+    }
+
+    /// O(1).
+    /// Add an edge from `from` to `to` with capacity `cap` and cost `cost` per unit flow, and return its index.
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: Cap, cost: Cost) -> usize {
+        self.lower.push(Cap::zero());
+        self.push_edge(from, to, cap, cost)
+    }
+
+    /// O(1).
+    /// Add an edge whose flow must be in `lower..=upper`, and return its index.
+    pub fn add_edge_with_lower_bound(
+        &mut self,
+        from: usize,
+        to: usize,
+        lower: Cap,
+        upper: Cap,
+        cost: Cost,
+    ) -> usize {
+        assert!(Cap::zero() <= lower && lower <= upper);
+        self.lower.push(lower);
+        self.lower_out[from] = self.lower_out[from] + lower;
+        self.lower_in[to] = self.lower_in[to] + lower;
+        self.push_edge(from, to, upper - lower, cost)
+    }
+
+    /// O(1).
+    /// Get the `i`-th edge with the current flow.
+    pub fn get_edge(&self, i: usize) -> Edge<Cap, Cost> {
+        let (u, k) = self.pos[i];
+        let e = &self.g[u][k];
+        let r = &self.g[e.to][e.rev];
+        let lower = if self.lower_applied {
+            self.lower[i]
+        } else {
+            Cap::zero()
+        };
+        Edge {
+            from: u,
+            to: e.to,
+            cap: e.cap + r.cap + self.lower[i],
+            flow: r.cap + lower,
+            cost: e.cost,
+        }
+    }
+
+    /// O(m).
+    /// All edges in the order of addition
+    pub fn edges(&self) -> Vec<Edge<Cap, Cost>> {
+        (0..self.pos.len()).map(|i| self.get_edge(i)).collect()
+    }
+
+    /// O(F (n + m) log n + nm).
+    /// Send flow from `s` to `t` up to `limit` with the minimum cost, and return `(flow, cost)`.
+    pub fn flow(&mut self, s: usize, t: usize, limit: Cap) -> (Cap, Cost) {
+        *self.slope_limit(s, t, limit).last().unwrap()
+    }
+
+    /// O(F (n + m) log n + nm).
+    /// Send as much flow as possible from `s` to `t` with the minimum cost,
+    /// and return the breakpoints `(flow, cost)` of the piecewise-linear cost curve, starting with `(0, 0)`.
+    pub fn slope(&mut self, s: usize, t: usize) -> Vec<(Cap, Cost)> {
+        self.slope_limit(s, t, Cap::max_value())
+    }
+
+    /// O(F (n + m) log n + nm).
+    /// `slope` with the amount of flow limited to `limit`.
+    pub fn slope_limit(&mut self, s: usize, t: usize, limit: Cap) -> Vec<(Cap, Cost)> {
+        assert_ne!(s, t);
+        let n = self.len();
+        let mut h = self.potential();
+        let mut ret = vec![(Cap::zero(), Cost::zero())];
+        let mut flow = Cap::zero();
+        let mut cost = Cost::zero();
+        let mut prev_d = None;
+
+        while flow < limit {
+            // Dijkstra on reduced costs, which are non-negative
+            let mut dist: Vec<Option<Cost>> = vec![None; n];
+            let mut prev = vec![(usize::MAX, usize::MAX); n];
+            let mut q = BinaryHeap::new();
+            dist[s] = Some(Cost::zero());
+            q.push(Reverse((Cost::zero(), s)));
+            while let Some(Reverse((d, u))) = q.pop() {
+                if dist[u] != Some(d) {
+                    continue;
+                }
+                for (i, e) in self.g[u].iter().enumerate() {
+                    if e.cap == Cap::zero() {
+                        continue;
+                    }
+                    let nd = d + e.cost + h[u] - h[e.to];
+                    if dist[e.to].is_none_or(|x| nd < x) {
+                        dist[e.to] = Some(nd);
+                        prev[e.to] = (u, i);
+                        q.push(Reverse((nd, e.to)));
+                    }
+                }
+            }
+            if dist[t].is_none() {
+                break;
+            }
+            for (hv, dv) in h.iter_mut().zip(&dist) {
+                if let Some(d) = *dv {
+                    *hv = *hv + d;
+                }
+            }
+
+            let mut f = limit - flow;
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                f = f.min(self.g[u][i].cap);
+                v = u;
+            }
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                let rev = self.g[u][i].rev;
+                self.g[u][i].cap = self.g[u][i].cap - f;
+                self.g[v][rev].cap = self.g[v][rev].cap + f;
+                v = u;
+            }
+
+            let d = h[t] - h[s];
+            flow = flow + f;
+            cost = cost + cast::<Cap, Cost>(f).unwrap() * d;
+            if prev_d == Some(d) {
+                ret.pop();
+            }
+            ret.push((flow, cost));
+            prev_d = Some(d);
+        }
+        ret
+    }
+
+    /// Potential such that reduced costs of edges with positive residual capacity are non-negative,
+    /// by Bellman-Ford algorithm from a virtual source
+    fn potential(&self) -> Vec<Cost> {
+        let n = self.len();
+        let mut h = vec![Cost::zero(); n];
+        for _ in 0..=n {
+            let mut updated = false;
+            for u in 0..n {
+                for e in &self.g[u] {
+                    if e.cap > Cap::zero() && h[u] + e.cost < h[e.to] {
+                        h[e.to] = h[u] + e.cost;
+                        updated = true;
+                    }
+                }
+            }
+            if !updated {
+                return h;
+            }
+        }
+        panic!("negative cycle");
+    }
+
+    /// Send exactly `amount` of flow from `s` to `t` satisfying lower bounds of all edges with the minimum cost.
+    /// Returns the cost including that of lower bounds, or `None` if there is no such flow.
+    ///
+    /// On failure the flow is left unchanged, so this can be called again.
+    /// After it succeeds, it must not be called again because the lower bounds are already sent.
+    ///
+    /// ```
+    /// # use competitive::graph::min_cost_flow::MinCostFlow;
+    /// let mut mcf = MinCostFlow::<i64, i64>::new(3);
+    /// mcf.add_edge(0, 1, 5, 1);
+    /// mcf.add_edge(0, 2, 5, 4);
+    /// mcf.add_edge_with_lower_bound(2, 1, 2, 5, 1);
+    /// assert_eq!(mcf.flow_with_lower_bounds(0, 1, 1), None);
+    /// assert_eq!(mcf.flow_with_lower_bounds(0, 1, 3), Some(11));
+    /// ```
+    pub fn flow_with_lower_bounds(&mut self, s: usize, t: usize, amount: Cap) -> Option<Cost> {
+        assert!(!self.lower_applied, "lower bounds are already sent");
+        let n = self.len();
+        let m = self.pos.len();
+        let caps = self
+            .g
+            .iter()
+            .map(|es| es.iter().map(|e| e.cap).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // circulation with an edge from t to s whose flow is exactly amount
+        let mut lower_in = self.lower_in.clone();
+        let mut lower_out = self.lower_out.clone();
+        lower_in[s] = lower_in[s] + amount;
+        lower_out[t] = lower_out[t] + amount;
+
+        let (ss, tt) = (n, n + 1);
+        self.g.push(vec![]);
+        self.g.push(vec![]);
+        let mut need = Cap::zero();
+        for v in 0..n {
+            if lower_in[v] > lower_out[v] {
+                self.push_edge(ss, v, lower_in[v] - lower_out[v], Cost::zero());
+                need = need + (lower_in[v] - lower_out[v]);
+            } else if lower_in[v] < lower_out[v] {
+                self.push_edge(v, tt, lower_out[v] - lower_in[v], Cost::zero());
+            }
+        }
+
+        let (f, cost) = *self.slope_limit(ss, tt, need).last().unwrap();
+
+        // remove the auxiliary edges, which are the last ones in each adjacency list
+        for i in (m..self.pos.len()).rev() {
+            let (u, k) = self.pos[i];
+            let to = self.g[u][k].to;
+            self.g[u].pop();
+            self.g[to].pop();
+        }
+        self.pos.truncate(m);
+        self.g.truncate(n);
+
+        if f < need {
+            // undo the partial flow
+            for (es, cs) in self.g.iter_mut().zip(caps) {
+                for (e, c) in es.iter_mut().zip(cs) {
+                    e.cap = c;
+                }
+            }
+            return None;
+        }
+        self.lower_applied = true;
+        let base = (0..m).fold(Cost::zero(), |acc, i| {
+            let (u, k) = self.pos[i];
+            acc + cast::<Cap, Cost>(self.lower[i]).unwrap() * self.g[u][k].cost
+        });
+        Some(base + cost)
+    }
+}
+
+#[test]
+fn min_cost_flow_test() {
+    use super::max_flow::MaxFlow;
+
+    // minimum cost of each amount of flow by enumerating flows of all edges
+    fn brute(
+        n: usize,
+        edges: &[(usize, usize, i64, i64, i64)],
+        s: usize,
+        t: usize,
+    ) -> Vec<Option<i64>> {
+        let mut ret = vec![None; 20];
+        let mut flow = edges.iter().map(|e| e.2).collect::<Vec<_>>();
+        loop {
+            let mut excess = vec![0; n];
+            for (e, &f) in edges.iter().zip(&flow) {
+                excess[e.0] -= f;
+                excess[e.1] += f;
+            }
+            if (0..n).all(|v| v == s || v == t || excess[v] == 0) && excess[t] >= 0 {
+                let cost = edges.iter().zip(&flow).map(|(e, &f)| e.4 * f).sum::<i64>();
+                let r = &mut ret[excess[t] as usize];
+                *r = Some(r.map_or(cost, |c: i64| c.min(cost)));
+            }
+
+            // next combination
+            let mut i = 0;
+            while i < edges.len() && flow[i] == edges[i].3 {
+                flow[i] = edges[i].2;
+                i += 1;
+            }
+            if i == edges.len() {
+                break;
+            }
+            flow[i] += 1;
+        }
+        ret
+    }
+
+    let mut x = 88172645463325252_u64;
+    let mut rand = |m: u64| {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        (x % m) as i64
+    };
+
+    for seed in 0..200 {
+        let n = 2 + (seed % 4) as usize;
+        let (s, t) = (0, n - 1);
+        let edges = (0..4 + seed % 4)
+            .map(|_| {
+                let (u, v) = (rand(n as u64) as usize, rand(n as u64) as usize);
+                // negative costs only on edges from smaller to larger nodes,
+                // and other edges are expensive enough to avoid negative cycles
+                let cost = if u < v { rand(9) - 4 } else { rand(5) + 12 };
+                let lower = if seed % 2 == 0 { 0 } else { rand(3) / 2 };
+                (u, v, lower, lower + rand(4), cost)
+            })
+            .collect::<Vec<_>>();
+
+        let expected = brute(n, &edges, s, t);
+        let len = expected
+            .iter()
+            .rposition(|e| e.is_some())
+            .map_or(0, |k| k + 1);
+        for (k, &e) in expected.iter().enumerate().take(len + 1) {
+            let mut mcf = MinCostFlow::<i64, i64>::new(n);
+            for &(u, v, l, r, c) in &edges {
+                mcf.add_edge_with_lower_bound(u, v, l, r, c);
+            }
+            assert_eq!(mcf.flow_with_lower_bounds(s, t, k as i64), e);
+            if e.is_some() {
+                // flows of edges are consistent with the cost
+                let es = mcf.edges();
+                let cost = es.iter().map(|e| e.cost * e.flow).sum::<i64>();
+                assert_eq!(Some(cost), e);
+                assert!(es
+                    .iter()
+                    .zip(&edges)
+                    .all(|(e, x)| x.2 <= e.flow && e.flow <= x.3));
+            }
+        }
+
+        if seed % 2 == 0 {
+            let mut mcf = MinCostFlow::<i64, i64>::new(n);
+            let mut mf = MaxFlow::<i64>::new(n);
+            for &(u, v, _, r, c) in &edges {
+                mcf.add_edge(u, v, r, c);
+                mf.add_edge(u, v, r);
+            }
+            let max = mf.flow(s, t);
+            let slope = mcf.clone().slope(s, t);
+            assert_eq!(slope.last().unwrap().0, max);
+            assert!(slope.windows(3).all(|w| {
+                (w[1].1 - w[0].1) * (w[2].0 - w[1].0) < (w[2].1 - w[1].1) * (w[1].0 - w[0].0)
+            }));
+
+            // cost of each amount on the cost curve
+            for k in 0..=max {
+                let i = slope.iter().position(|p| p.0 >= k).unwrap();
+                let c = if slope[i].0 == k {
+                    slope[i].1
+                } else {
+                    let (a, b) = (slope[i - 1], slope[i]);
+                    a.1 + (b.1 - a.1) / (b.0 - a.0) * (k - a.0)
+                };
+                assert_eq!(Some(c), expected[k as usize]);
+                assert_eq!(mcf.clone().flow(s, t, k), (k, c));
+            }
+        }
+    }
+
+    // flow can be continued after a limited flow
+    let mut mcf = MinCostFlow::<u32, i64>::new(3);
+    mcf.add_edge(0, 1, 3, -2);
+    mcf.add_edge(1, 2, 2, 5);
+    mcf.add_edge(0, 2, 4, 4);
+    assert_eq!(mcf.flow(0, 2, 1), (1, 3));
+    assert_eq!(mcf.flow(0, 2, 10), (5, 19));
+    assert_eq!(mcf.get_edge(0).flow, 2);
+
+    // lower bounds are not counted as flow unless flow_with_lower_bounds succeeds
+    let mut mcf = MinCostFlow::<i64, i64>::new(3);
+    mcf.add_edge(0, 1, 5, 1);
+    let e = mcf.add_edge_with_lower_bound(1, 2, 2, 5, 1);
+    mcf.add_edge_with_lower_bound(2, 0, 1, 1, 0);
+    let mut plain = mcf.clone();
+    assert_eq!(plain.flow(0, 2, 3), (3, 6));
+    assert_eq!(plain.get_edge(e).flow, 3);
+    assert_eq!(plain.get_edge(e).cap, 5);
+    assert_eq!(mcf.flow_with_lower_bounds(0, 1, 1), None);
+    assert!(mcf.edges().iter().all(|e| e.flow == 0));
+    // one unit circulates through 2 -> 0
+    assert_eq!(mcf.flow_with_lower_bounds(0, 2, 3), Some(8));
+    assert_eq!(mcf.get_edge(e).flow, 4);
+    assert_eq!(mcf.get_edge(e + 1).flow, 1);
+}
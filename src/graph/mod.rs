@@ -0,0 +1,575 @@
+pub mod centroid;
+pub mod dag;
+pub mod hld;
+pub mod max_flow;
+pub mod min_cost_flow;
+pub mod rerooting;
+pub mod scc;
+pub mod shortest_path;
+pub mod tree;
+
+use crate::inf::MaybeInf::{self, *};
+use proconio::marker::Usize1;
+use proconio::source::{Readable, Source};
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::marker::PhantomData;
+
+/// Graph trait
+pub trait Graph<'a> {
+    type NodeId: Copy;
+    type Iter: Iterator<Item = Self::NodeId>;
+    fn len(&self) -> usize;
+    fn index(&self, a: Self::NodeId) -> usize;
+    fn neighbors(&'a self, a: Self::NodeId) -> Self::Iter;
+}
+
+/// Graph with weighted edges
+pub trait WGraph<'a>: Graph<'a> {
+    type Weight;
+    type WIter: Iterator<Item = (Self::NodeId, Self::Weight)>;
+    fn weighted_neighbors(&'a self, a: Self::NodeId) -> Self::WIter;
+}
+
+// Markers
+
+/// Read marker for undirected adjacency list graph
+///
+/// The result type is `Vec<Vec<usize>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize m:usize
+/// u_1:IndexType v_1:IndexType
+/// ...
+/// u_m:IndexType v_m:IndexType
+/// ```
+pub struct ListGraph<IndexType = Usize1>(PhantomData<IndexType>);
+
+impl<IndexType: Readable<Output = usize>> Readable for ListGraph<IndexType> {
+    type Output = Vec<Vec<usize>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let m = usize::read(source);
+        let mut g = vec![vec![]; n];
+
+        for _ in 0..m {
+            let u = IndexType::read(source);
+            let v = IndexType::read(source);
+            g[u].push(v);
+            g[v].push(u);
+        }
+
+        g
+    }
+}
+
+/// Read marker for undirected adjacency matrix graph
+///
+/// The result type is `Vec<Vec<bool>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize m:usize
+/// u_1:IndexType v_1:IndexType
+/// ...
+/// u_m:IndexType v_m:IndexType
+/// ```
+pub struct MatGraph<IndexType = Usize1>(PhantomData<IndexType>);
+
+impl<IndexType: Readable<Output = usize>> Readable for MatGraph<IndexType> {
+    type Output = Vec<Vec<bool>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let m = usize::read(source);
+        let mut g = vec![vec![false; n]; n];
+
+        for _ in 0..m {
+            let u = IndexType::read(source);
+            let v = IndexType::read(source);
+            g[u][v] = true;
+            g[v][u] = true;
+        }
+
+        g
+    }
+}
+
+/// Read marker for undirected adjacency list tree
+///
+/// The result type is `Vec<Vec<usize>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize
+/// u_1:IndexType v_1:IndexType
+/// ...
+/// u_{n-1}:IndexType v_{n-1}:IndexType
+/// ```
+pub struct ListTree<IndexType = Usize1>(PhantomData<IndexType>);
+
+impl<IndexType: Readable<Output = usize>> Readable for ListTree<IndexType> {
+    type Output = Vec<Vec<usize>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let mut g = vec![vec![]; n];
+
+        for _ in 0..n - 1 {
+            let u = IndexType::read(source);
+            let v = IndexType::read(source);
+            g[u].push(v);
+            g[v].push(u);
+        }
+
+        g
+    }
+}
+
+/// Read marker for directed adjacency list graph
+///
+/// The result type is `Vec<Vec<usize>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize m:usize
+/// u_1:IndexType v_1:IndexType
+/// ...
+/// u_m:IndexType v_m:IndexType
+/// ```
+pub struct DiListGraph<IndexType = Usize1>(PhantomData<IndexType>);
+
+impl<IndexType: Readable<Output = usize>> Readable for DiListGraph<IndexType> {
+    type Output = Vec<Vec<usize>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let m = usize::read(source);
+        let mut g = vec![vec![]; n];
+
+        for _ in 0..m {
+            let u = IndexType::read(source);
+            let v = IndexType::read(source);
+            g[u].push(v);
+        }
+
+        g
+    }
+}
+
+/// Read marker for directed adjacency list tree, whose edges are directed from `u_i` to `v_i`
+///
+/// The result type is `Vec<Vec<usize>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize
+/// u_1:IndexType v_1:IndexType
+/// ...
+/// u_{n-1}:IndexType v_{n-1}:IndexType
+/// ```
+pub struct DiListTree<IndexType = Usize1>(PhantomData<IndexType>);
+
+impl<IndexType: Readable<Output = usize>> Readable for DiListTree<IndexType> {
+    type Output = Vec<Vec<usize>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let mut g = vec![vec![]; n];
+
+        for _ in 0..n - 1 {
+            let u = IndexType::read(source);
+            let v = IndexType::read(source);
+            g[u].push(v);
+        }
+
+        g
+    }
+}
+
+/// Read marker for undirected weighted adjacency list graph
+///
+/// The result type is `Vec<Vec<(usize, W::Output)>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize m:usize
+/// u_1:IndexType v_1:IndexType w_1:W
+/// ...
+/// u_m:IndexType v_m:IndexType w_m:W
+/// ```
+pub struct WListGraph<W, IndexType = Usize1>(PhantomData<(W, IndexType)>);
+
+impl<W: Readable, IndexType: Readable<Output = usize>> Readable for WListGraph<W, IndexType>
+where
+    W::Output: Clone,
+{
+    type Output = Vec<Vec<(usize, W::Output)>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let m = usize::read(source);
+        let mut g = vec![vec![]; n];
+
+        for _ in 0..m {
+            let u = IndexType::read(source);
+            let v = IndexType::read(source);
+            let w = W::read(source);
+            g[u].push((v, w.clone()));
+            g[v].push((u, w));
+        }
+
+        g
+    }
+}
+
+/// Read marker for directed weighted adjacency list graph
+///
+/// The result type is `Vec<Vec<(usize, W::Output)>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize m:usize
+/// u_1:IndexType v_1:IndexType w_1:W
+/// ...
+/// u_m:IndexType v_m:IndexType w_m:W
+/// ```
+pub struct WDiListGraph<W, IndexType = Usize1>(PhantomData<(W, IndexType)>);
+
+impl<W: Readable, IndexType: Readable<Output = usize>> Readable for WDiListGraph<W, IndexType> {
+    type Output = Vec<Vec<(usize, W::Output)>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let m = usize::read(source);
+        let mut g = (0..n).map(|_| vec![]).collect::<Vec<_>>();
+
+        for _ in 0..m {
+            let u = IndexType::read(source);
+            let v = IndexType::read(source);
+            let w = W::read(source);
+            g[u].push((v, w));
+        }
+
+        g
+    }
+}
+
+/// Read marker for undirected weighted adjacency list tree
+///
+/// The result type is `Vec<Vec<(usize, W::Output)>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize
+/// u_1:IndexType v_1:IndexType w_1:W
+/// ...
+/// u_{n-1}:IndexType v_{n-1}:IndexType w_{n-1}:W
+/// ```
+pub struct WListTree<W, IndexType = Usize1>(PhantomData<(W, IndexType)>);
+
+impl<W: Readable, IndexType: Readable<Output = usize>> Readable for WListTree<W, IndexType>
+where
+    W::Output: Clone,
+{
+    type Output = Vec<Vec<(usize, W::Output)>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let mut g = vec![vec![]; n];
+
+        for _ in 0..n - 1 {
+            let u = IndexType::read(source);
+            let v = IndexType::read(source);
+            let w = W::read(source);
+            g[u].push((v, w.clone()));
+            g[v].push((u, w));
+        }
+
+        g
+    }
+}
+
+/// Read marker for directed weighted adjacency list tree, whose edges are directed from `u_i` to `v_i`
+///
+/// The result type is `Vec<Vec<(usize, W::Output)>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize
+/// u_1:IndexType v_1:IndexType w_1:W
+/// ...
+/// u_{n-1}:IndexType v_{n-1}:IndexType w_{n-1}:W
+/// ```
+pub struct WDiListTree<W, IndexType = Usize1>(PhantomData<(W, IndexType)>);
+
+impl<W: Readable, IndexType: Readable<Output = usize>> Readable for WDiListTree<W, IndexType> {
+    type Output = Vec<Vec<(usize, W::Output)>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let mut g = (0..n).map(|_| vec![]).collect::<Vec<_>>();
+
+        for _ in 0..n - 1 {
+            let u = IndexType::read(source);
+            let v = IndexType::read(source);
+            let w = W::read(source);
+            g[u].push((v, w));
+        }
+
+        g
+    }
+}
+
+/// Read marker for undirected tree described by the parent of each node except the root `0`
+///
+/// The result type is `Vec<Vec<usize>>`
+///
+/// It reads input like below:
+///
+/// ```ignore
+/// n:usize
+/// p_2:IndexType p_3:IndexType ... p_n:IndexType
+/// ```
+pub struct ParentTree<IndexType = Usize1>(PhantomData<IndexType>);
+
+impl<IndexType: Readable<Output = usize>> Readable for ParentTree<IndexType> {
+    type Output = Vec<Vec<usize>>;
+
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let n = usize::read(source);
+        let mut g = vec![vec![]; n];
+
+        for v in 1..n {
+            let p = IndexType::read(source);
+            g[p].push(v);
+            g[v].push(p);
+        }
+
+        g
+    }
+}
+
+#[test]
+fn markers_test() {
+    use proconio::source::once::OnceSource;
+
+    let mut source = OnceSource::from("3 2\n1 2\n3 2\n");
+    assert_eq!(
+        DiListGraph::<Usize1>::read(&mut source),
+        vec![vec![1], vec![], vec![1]]
+    );
+
+    let mut source = OnceSource::from("3 2\n1 2 10\n3 2 -5\n");
+    assert_eq!(
+        WListGraph::<i64>::read(&mut source),
+        vec![vec![(1, 10)], vec![(0, 10), (2, -5)], vec![(1, -5)]]
+    );
+
+    let mut source = OnceSource::from("3\n0 1 7\n0 2 8\n");
+    assert_eq!(
+        WDiListTree::<u32, usize>::read(&mut source),
+        vec![vec![(1, 7), (2, 8)], vec![], vec![]]
+    );
+
+    let mut source = OnceSource::from("4\n1 1 2\n");
+    assert_eq!(
+        ParentTree::<Usize1>::read(&mut source),
+        vec![vec![1, 2], vec![0, 3], vec![0], vec![1]]
+    );
+}
+
+//-----
+
+impl<'a> Graph<'a> for UnweightedGraph {
+    type NodeId = usize;
+    type Iter = std::iter::Cloned<std::slice::Iter<'a, Self::NodeId>>;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn index(&self, a: Self::NodeId) -> usize {
+        a
+    }
+
+    fn neighbors(&'a self, a: Self::NodeId) -> Self::Iter {
+        self[a].iter().cloned()
+    }
+}
+
+/// Edges of unweighted graphs have the unit weight `()`.
+impl<'a> WGraph<'a> for UnweightedGraph {
+    type Weight = ();
+    type WIter = std::iter::Map<std::slice::Iter<'a, usize>, fn(&usize) -> (usize, ())>;
+
+    fn weighted_neighbors(&'a self, a: Self::NodeId) -> Self::WIter {
+        self[a].iter().map(|&v| (v, ()))
+    }
+}
+
+pub type UnweightedGraph = Vec<Vec<usize>>;
+pub type WeightedGraph<W> = Vec<Vec<(usize, W)>>;
+
+impl<'a, W: 'a> Graph<'a> for WeightedGraph<W> {
+    type NodeId = usize;
+    type Iter = std::iter::Map<std::slice::Iter<'a, (usize, W)>, fn(&(usize, W)) -> usize>;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn index(&self, a: Self::NodeId) -> usize {
+        a
+    }
+
+    fn neighbors(&'a self, a: Self::NodeId) -> Self::Iter {
+        self[a].iter().map(|e| e.0)
+    }
+}
+
+impl<'a, W: 'a + Clone> WGraph<'a> for WeightedGraph<W> {
+    type Weight = W;
+    type WIter = std::iter::Cloned<std::slice::Iter<'a, (usize, W)>>;
+
+    fn weighted_neighbors(&'a self, a: Self::NodeId) -> Self::WIter {
+        self[a].iter().cloned()
+    }
+}
+
+pub fn make_directed_graph(n: usize, edges: &[(usize, usize)]) -> UnweightedGraph {
+    let mut g = vec![vec![]; n];
+    for &(u, v) in edges.iter() {
+        g[u].push(v);
+    }
+    g
+}
+
+pub fn make_undirected_graph(n: usize, edges: &[(usize, usize)]) -> UnweightedGraph {
+    let mut g = vec![vec![]; n];
+    for &(u, v) in edges.iter() {
+        g[u].push(v);
+        g[v].push(u);
+    }
+    g
+}
+
+pub fn make_weighted_directed_graph<W: Clone>(
+    n: usize,
+    edges: &[(usize, usize, W)],
+) -> WeightedGraph<W> {
+    let mut g = vec![vec![]; n];
+    for &(u, v, ref w) in edges.iter() {
+        g[u].push((v, w.clone()));
+    }
+    g
+}
+
+pub fn make_weighted_undirected_graph<W: Clone>(
+    n: usize,
+    edges: &[(usize, usize, W)],
+) -> WeightedGraph<W> {
+    let mut g = vec![vec![]; n];
+    for &(u, v, ref w) in edges.iter() {
+        g[u].push((v, w.clone()));
+        g[v].push((u, w.clone()));
+    }
+    g
+}
+
+// struct Map2DGraph<C> {
+//     bd: Vec<Vec<C>>,
+// }
+
+// impl<'a, T> Graph<'a> for Map2DGraph<T> {
+//     type NodeId = (usize, usize);
+//     type Iter = std::slice::Iter<'a, Self::NodeId>;
+
+//     fn len(&self) -> usize {
+//         self.bd.len() * self.bd[0].len()
+//     }
+
+//     fn index(&self, ix: Self::NodeId) -> usize {
+//         ix.0 * self.bd.len() + ix.1
+//     }
+
+//     fn neighbors(&'a self, a: Self::NodeId) -> Self::Iter {
+//         let h = self.bd.len();
+//         let w = self.bd[0].len();
+
+//         const VECT: &[(i64, i64)] = &[(0, 1), (0, -1), (1, 0), (-1, 0)];
+
+//         // VECT.iter()
+//         //     .map(|&(di, dj)| (a.0 as i64 + di, a.1 as i64 + dj))
+//         //     .filter(|&(i, j)| i >= 0 && i < h && j >= 0 && j < w)
+//         //     .map(|&(i, j)| (i as usize, j as usize))
+
+//         todo!()
+//     }
+// }
+
+//-----
+
+pub struct Bfs<'a, G: Graph<'a>> {
+    visited: Vec<bool>,
+    q: VecDeque<(G::NodeId, Option<G::NodeId>)>,
+    g: &'a G,
+}
+
+impl<'a, G: Graph<'a>> Iterator for Bfs<'a, G> {
+    type Item = (G::NodeId, G::NodeId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((u, prev)) = self.q.pop_front() {
+            for v in self.g.neighbors(u) {
+                if !self.visited[self.g.index(v)] {
+                    self.visited[self.g.index(v)] = true;
+                    self.q.push_back((v, Some(u)));
+                }
+            }
+
+            if let Some(prev) = prev {
+                Some((prev, u))
+            } else {
+                self.next()
+            }
+        } else {
+            None
+        }
+    }
+}
+
+pub fn bfs<'a, G: Graph<'a, NodeId = usize>>(g: &'a G, start: G::NodeId) -> Bfs<'a, G> {
+    let n = g.len();
+    let mut visited = vec![false; n];
+    let mut q = VecDeque::new();
+    visited[start] = true;
+    q.push_back((start, None));
+
+    Bfs { visited, q, g }
+}
+
+/// Returns a vector which stores distances from `start`.
+/// For unreachable node, `usize::MAX` is stored.
+pub fn make_dist_table<'a, G: Graph<'a, NodeId = usize>>(
+    g: &'a G,
+    start: G::NodeId,
+) -> Vec<MaybeInf<usize>> {
+    let mut dist = vec![Inf; g.len()];
+    dist[start] = NonInf(0);
+    for (u, v) in bfs(g, start) {
+        dist[v] = dist[u] + 1;
+    }
+    dist
+}
@@ -0,0 +1,175 @@
+use super::WGraph;
+use crate::monoid::Monoid;
+
+/// O(n).
+/// Rerooting DP. Returns the DP value of the whole tree rooted at each node.
+///
+/// The value of the subtree rooted at `v` is defined as
+/// `add_root(mconcat([edge_lift(value of c, c, v, w) for each child c of v]), v)`,
+/// where `w` is the weight of the edge between `c` and `v`.
+/// Children are folded in the order of the adjacency list of `v`.
+///
+/// Unweighted graphs can be used as well, with `()` as the weight.
+///
+/// ```
+/// # use competitive::graph::make_undirected_graph;
+/// # use competitive::graph::rerooting::rerooting;
+/// # use competitive::monoid::Max;
+/// // height of the tree rooted at each node
+/// let g = make_undirected_graph(4, &[(0, 1), (1, 2), (1, 3)]);
+/// let h = rerooting(&g, |x: &Max<usize>, _| *x, |x, _, _, _| Max(x.0 + 1));
+/// assert_eq!(h.iter().map(|x| x.0).collect::<Vec<_>>(), vec![2, 1, 2, 2]);
+/// ```
+pub fn rerooting<'a, G, T>(
+    g: &'a G,
+    add_root: impl Fn(&T, usize) -> T,
+    edge_lift: impl Fn(&T, usize, usize, G::Weight) -> T,
+) -> Vec<T>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone,
+    T: Clone + Monoid,
+{
+    let n = g.len();
+    if n == 0 {
+        return vec![];
+    }
+
+    // BFS order from node 0
+    let mut order = vec![0];
+    let mut parent = vec![usize::MAX; n];
+    let mut i = 0;
+    while i < order.len() {
+        let u = order[i];
+        for v in g.neighbors(u) {
+            if v != parent[u] {
+                parent[v] = u;
+                order.push(v);
+            }
+        }
+        i += 1;
+    }
+
+    // down[v]: value of the subtree rooted at v
+    let mut down = vec![T::mempty(); n];
+    for &u in order.iter().rev() {
+        let acc = g
+            .weighted_neighbors(u)
+            .filter(|&(v, _)| v != parent[u])
+            .fold(T::mempty(), |acc, (v, w)| {
+                T::mappend(&acc, &edge_lift(&down[v], v, u, w))
+            });
+        down[u] = add_root(&acc, u);
+    }
+
+    // up[v]: value of the tree rooted at parent[v] without the subtree of v
+    let mut up = vec![T::mempty(); n];
+    let mut ret = vec![T::mempty(); n];
+    for &u in &order {
+        let es = g.weighted_neighbors(u).collect::<Vec<_>>();
+        let lifted = es
+            .iter()
+            .map(|(v, w)| {
+                if *v == parent[u] {
+                    edge_lift(&up[u], *v, u, w.clone())
+                } else {
+                    edge_lift(&down[*v], *v, u, w.clone())
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // suffix[i] = lifted[i] + ... + lifted[k - 1]
+        let k = lifted.len();
+        let mut suffix = vec![T::mempty(); k + 1];
+        for i in (0..k).rev() {
+            suffix[i] = T::mappend(&lifted[i], &suffix[i + 1]);
+        }
+        ret[u] = add_root(&suffix[0], u);
+
+        let mut prefix = T::mempty();
+        for (i, (v, _)) in es.iter().enumerate() {
+            if *v != parent[u] {
+                up[*v] = add_root(&T::mappend(&prefix, &suffix[i + 1]), u);
+            }
+            prefix = T::mappend(&prefix, &lifted[i]);
+        }
+    }
+    ret
+}
+
+#[test]
+fn rerooting_test() {
+    use super::shortest_path::dijkstra;
+    use super::{make_dist_table, make_undirected_graph, make_weighted_undirected_graph};
+    use crate::monoid::Max;
+
+    // (number of nodes, sum of distances)
+    #[derive(Clone, Debug)]
+    struct Sd(usize, u64);
+    impl Monoid for Sd {
+        fn mempty() -> Self {
+            Sd(0, 0)
+        }
+        fn mappend(l: &Self, r: &Self) -> Self {
+            Sd(l.0 + r.0, l.1 + r.1)
+        }
+    }
+
+    // pre-order sequence of nodes, which is not commutative
+    #[derive(Clone, Debug)]
+    struct Seq(Vec<usize>);
+    impl Monoid for Seq {
+        fn mempty() -> Self {
+            Seq(vec![])
+        }
+        fn mappend(l: &Self, r: &Self) -> Self {
+            Seq(l.0.iter().chain(r.0.iter()).cloned().collect())
+        }
+    }
+
+    for seed in 0..20 {
+        let n = 1 + seed * 3;
+        let edges = (1..n)
+            .map(|v| ((v * 7 + seed) % v, v, ((v * 13 + seed) % 5) as u64))
+            .collect::<Vec<_>>();
+        let g = make_undirected_graph(
+            n,
+            &edges.iter().map(|&(u, v, _)| (u, v)).collect::<Vec<_>>(),
+        );
+        let wg = make_weighted_undirected_graph(n, &edges);
+
+        let sd = rerooting(
+            &g,
+            |x: &Sd, _| Sd(x.0 + 1, x.1),
+            |x, _, _, ()| Sd(x.0, x.1 + x.0 as u64),
+        );
+        let ecc = rerooting(&wg, |x: &Max<u64>, _| *x, |x, _, _, w| Max(x.0 + w));
+        let pre = rerooting(
+            &g,
+            |x: &Seq, v| Seq(std::iter::once(v).chain(x.0.iter().cloned()).collect()),
+            |x, _, _, ()| x.clone(),
+        );
+
+        for s in 0..n {
+            let dist = make_dist_table(&g, s);
+            assert_eq!(sd[s].0, n);
+            assert_eq!(sd[s].1, dist.iter().map(|d| d.unwrap() as u64).sum::<u64>());
+
+            let wdist = dijkstra(&wg, s).dist;
+            assert_eq!(ecc[s].0, wdist.iter().map(|d| d.unwrap()).max().unwrap());
+
+            // pre-order by recursion-free DFS visiting neighbors in adjacency order
+            let mut expected = vec![];
+            let mut stack = vec![(s, usize::MAX)];
+            while let Some((u, p)) = stack.pop() {
+                expected.push(u);
+                for &v in g[u].iter().rev() {
+                    if v != p {
+                        stack.push((v, u));
+                    }
+                }
+            }
+            assert_eq!(pre[s].0, expected);
+        }
+    }
+}
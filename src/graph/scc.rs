@@ -0,0 +1,179 @@
+use super::{Graph, UnweightedGraph};
+
+/// Strongly connected components
+#[derive(Clone, Debug)]
+pub struct Scc {
+    /// `id[v]` is the index of the component containing `v`
+    pub id: Vec<usize>,
+    /// Components in topological order,
+    /// i.e. every edge goes from a component to the same or a later one
+    pub components: Vec<Vec<usize>>,
+}
+
+impl Scc {
+    /// Number of components
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns true if the graph has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// O(V + E log E).
+    /// DAG of components of `g` without self loops and multiple edges.
+    /// Node `i` of the result corresponds to `components[i]`.
+    pub fn condensation<'a, G: Graph<'a, NodeId = usize>>(&self, g: &'a G) -> UnweightedGraph {
+        let mut ret = vec![vec![]; self.len()];
+        for u in 0..g.len() {
+            for v in g.neighbors(u) {
+                if self.id[u] != self.id[v] {
+                    ret[self.id[u]].push(self.id[v]);
+                }
+            }
+        }
+        for es in ret.iter_mut() {
+            es.sort_unstable();
+            es.dedup();
+        }
+        ret
+    }
+}
+
+/// O(V + E).
+/// Strongly connected components by Tarjan's algorithm without recursion.
+///
+/// ```
+/// # use competitive::graph::make_directed_graph;
+/// # use competitive::graph::scc::strongly_connected_components;
+/// let g = make_directed_graph(4, &[(0, 1), (1, 0), (1, 2), (3, 2)]);
+/// let scc = strongly_connected_components(&g);
+/// assert_eq!(scc.len(), 3);
+/// assert_eq!(scc.id[0], scc.id[1]);
+/// assert!(scc.id[1] < scc.id[2] && scc.id[3] < scc.id[2]);
+/// ```
+pub fn strongly_connected_components<'a, G: Graph<'a, NodeId = usize>>(g: &'a G) -> Scc {
+    let n = g.len();
+    let mut ord = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut time = 0;
+
+    for s in 0..n {
+        if ord[s] != usize::MAX {
+            continue;
+        }
+
+        let mut dfs = vec![(s, g.neighbors(s))];
+        ord[s] = time;
+        low[s] = time;
+        time += 1;
+        stack.push(s);
+        on_stack[s] = true;
+
+        while let Some((u, it)) = dfs.last_mut() {
+            let u = *u;
+            if let Some(v) = it.next() {
+                if ord[v] == usize::MAX {
+                    ord[v] = time;
+                    low[v] = time;
+                    time += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                    dfs.push((v, g.neighbors(v)));
+                } else if on_stack[v] {
+                    low[u] = low[u].min(ord[v]);
+                }
+                continue;
+            }
+
+            dfs.pop();
+            if let Some(&(p, _)) = dfs.last() {
+                low[p] = low[p].min(low[u]);
+            }
+            if low[u] == ord[u] {
+                let mut c = vec![];
+                loop {
+                    let w = stack.pop().unwrap();
+                    on_stack[w] = false;
+                    c.push(w);
+                    if w == u {
+                        break;
+                    }
+                }
+                components.push(c);
+            }
+        }
+    }
+
+    // Tarjan's algorithm finds components in reverse topological order
+    components.reverse();
+    let mut id = vec![0; n];
+    for (i, c) in components.iter().enumerate() {
+        for &v in c {
+            id[v] = i;
+        }
+    }
+    Scc { id, components }
+}
+
+#[test]
+fn scc_test() {
+    use super::make_directed_graph;
+
+    for seed in 0..30 {
+        let n = 15;
+        let edges = (0..n + seed / 2)
+            .map(|i| ((i * 7 + seed) % n, (i * 11 + seed * 5 + 3) % n))
+            .collect::<Vec<_>>();
+        let g = make_directed_graph(n, &edges);
+        let scc = strongly_connected_components(&g);
+
+        // reachability by Warshall-Floyd
+        let mut reach = vec![vec![false; n]; n];
+        for (u, row) in reach.iter_mut().enumerate() {
+            row[u] = true;
+        }
+        for &(u, v) in &edges {
+            reach[u][v] = true;
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    reach[i][j] |= reach[i][k] && reach[k][j];
+                }
+            }
+        }
+
+        for (u, ru) in reach.iter().enumerate() {
+            assert!(scc.components[scc.id[u]].contains(&u));
+            for (v, rv) in reach.iter().enumerate() {
+                assert_eq!(scc.id[u] == scc.id[v], ru[v] && rv[u]);
+            }
+        }
+        assert_eq!(scc.components.iter().map(|c| c.len()).sum::<usize>(), n);
+
+        let dag = scc.condensation(&g);
+        for (c, es) in dag.iter().enumerate() {
+            assert!(es.windows(2).all(|e| e[0] < e[1]));
+            assert!(es.iter().all(|&d| c < d));
+        }
+        for &(u, v) in &edges {
+            if scc.id[u] != scc.id[v] {
+                assert!(dag[scc.id[u]].contains(&scc.id[v]));
+            }
+        }
+    }
+
+    // deep path does not overflow the stack
+    let n = 1_000_000;
+    let mut edges = (0..n - 1).map(|i| (i, i + 1)).collect::<Vec<_>>();
+    edges.push((n - 1, n / 2));
+    let scc = strongly_connected_components(&make_directed_graph(n, &edges));
+    assert_eq!(scc.len(), n / 2 + 1);
+    assert_eq!(scc.id[0], 0);
+    assert_eq!(scc.components[n / 2].len(), n / 2);
+}
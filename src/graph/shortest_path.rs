@@ -0,0 +1,681 @@
+use super::WGraph;
+use crate::inf::MaybeInf::{self, *};
+use num::Zero;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::ops::{Add, Sub};
+
+/// Result of single-source (or multi-source) shortest path algorithms
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<W> {
+    /// Distance from the nearest source, `Inf` if unreachable
+    pub dist: Vec<MaybeInf<W>>,
+    /// Previous node on a shortest path, `None` for sources and unreachable nodes
+    pub prev: Vec<Option<usize>>,
+}
+
+impl<W> ShortestPaths<W> {
+    /// O(length of path).
+    /// Nodes on a shortest path from a source to `v`, or `None` if `v` is unreachable.
+    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        if !matches!(self.dist[v], NonInf(_)) {
+            return None;
+        }
+        let mut ret = vec![v];
+        let mut cur = v;
+        while let Some(p) = self.prev[cur] {
+            ret.push(p);
+            cur = p;
+        }
+        ret.reverse();
+        Some(ret)
+    }
+}
+
+/// O((V + E) log V).
+/// Shortest paths from `start` by Dijkstra's algorithm. Weights must be non-negative.
+///
+/// ```
+/// # use competitive::graph::make_weighted_directed_graph;
+/// # use competitive::graph::shortest_path::dijkstra;
+/// # use competitive::inf::MaybeInf::*;
+/// let g = make_weighted_directed_graph(4, &[(0, 1, 5), (0, 2, 1), (2, 1, 2)]);
+/// let sp = dijkstra(&g, 0);
+/// assert_eq!(sp.dist, vec![NonInf(0), NonInf(3), NonInf(1), Inf]);
+/// assert_eq!(sp.path_to(1), Some(vec![0, 2, 1]));
+/// assert_eq!(sp.path_to(3), None);
+/// ```
+pub fn dijkstra<'a, G>(g: &'a G, start: usize) -> ShortestPaths<G::Weight>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Ord + Zero + Add<Output = G::Weight>,
+{
+    multi_source_dijkstra(g, &[start])
+}
+
+/// O((V + E) log V).
+/// Shortest paths from the nearest node of `starts` by Dijkstra's algorithm. Weights must be non-negative.
+pub fn multi_source_dijkstra<'a, G>(g: &'a G, starts: &[usize]) -> ShortestPaths<G::Weight>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Ord + Zero + Add<Output = G::Weight>,
+{
+    let n = g.len();
+    let mut dist = vec![Inf; n];
+    let mut prev = vec![None; n];
+    let mut q = BinaryHeap::new();
+
+    for &s in starts {
+        dist[s] = NonInf(G::Weight::zero());
+        q.push(Reverse((G::Weight::zero(), s)));
+    }
+
+    while let Some(Reverse((d, u))) = q.pop() {
+        if dist[u] < NonInf(d.clone()) {
+            continue;
+        }
+        for (v, w) in g.weighted_neighbors(u) {
+            let nd = d.clone() + w;
+            if NonInf(nd.clone()) < dist[v] {
+                dist[v] = NonInf(nd.clone());
+                prev[v] = Some(u);
+                q.push(Reverse((nd, v)));
+            }
+        }
+    }
+
+    ShortestPaths { dist, prev }
+}
+
+/// O(V^2).
+/// Shortest paths from `start` by Dijkstra's algorithm on an adjacency matrix.
+/// `mat[u][v]` is the weight of the edge from `u` to `v`, or `Inf` if there is no edge.
+/// Weights must be non-negative.
+pub fn dijkstra_dense<W>(mat: &[Vec<MaybeInf<W>>], start: usize) -> ShortestPaths<W>
+where
+    W: Clone + Ord + Zero + Add<Output = W>,
+{
+    let n = mat.len();
+    let mut dist = vec![Inf; n];
+    let mut prev = vec![None; n];
+    let mut used = vec![false; n];
+    dist[start] = NonInf(W::zero());
+
+    while let Some(u) = (0..n)
+        .filter(|&u| !used[u] && dist[u] != Inf)
+        .min_by_key(|&u| dist[u].clone())
+    {
+        used[u] = true;
+
+        for (v, w) in mat[u].iter().enumerate() {
+            if let NonInf(w) = w {
+                let nd = dist[u].clone() + w.clone();
+                if nd < dist[v] {
+                    dist[v] = nd;
+                    prev[v] = Some(u);
+                }
+            }
+        }
+    }
+
+    ShortestPaths { dist, prev }
+}
+
+#[test]
+fn dijkstra_test() {
+    use super::make_weighted_undirected_graph;
+
+    let n = 30;
+    let edges = (0..n * 3)
+        .map(|i| (i * 7 % n, (i * 13 + 5) % n, (i * 37 % 11) as u64))
+        .filter(|&(u, v, _)| u != v)
+        .collect::<Vec<_>>();
+    let g = make_weighted_undirected_graph(n + 1, &edges);
+
+    // reference by Warshall-Floyd
+    let mut mat = vec![vec![Inf; n + 1]; n + 1];
+    for (u, es) in g.iter().enumerate() {
+        mat[u][u] = NonInf(0);
+        for &(v, w) in es {
+            mat[u][v] = mat[u][v].min(NonInf(w));
+        }
+    }
+    let adj = mat.clone();
+    for k in 0..=n {
+        for i in 0..=n {
+            for j in 0..=n {
+                if mat[i][k] != Inf && mat[k][j] != Inf {
+                    mat[i][j] = mat[i][j].min(mat[i][k] + mat[k][j]);
+                }
+            }
+        }
+    }
+
+    for (s, ms) in mat.iter().enumerate() {
+        for sp in [dijkstra(&g, s), dijkstra_dense(&adj, s)] {
+            assert_eq!(&sp.dist, ms);
+            for v in 0..=n {
+                match sp.path_to(v) {
+                    Some(path) => {
+                        assert_eq!(path[0], s);
+                        assert_eq!(*path.last().unwrap(), v);
+                        let len = path.windows(2).map(|e| adj[e[0]][e[1]].unwrap()).sum();
+                        assert_eq!(sp.dist[v], NonInf(len));
+                    }
+                    None => assert_eq!(sp.dist[v], Inf),
+                }
+            }
+        }
+    }
+
+    let sp = multi_source_dijkstra(&g, &[0, 1, 2]);
+    for (v, &d) in sp.dist.iter().enumerate() {
+        assert_eq!(d, (0..3).map(|s| mat[s][v]).min().unwrap());
+        if let Some(path) = sp.path_to(v) {
+            assert!(path[0] < 3);
+        }
+    }
+}
+
+/// O(VE).
+/// Shortest distances from `start` by Bellman-Ford algorithm.
+/// Weights can be negative, and `NegInf` is stored for nodes reachable from a negative cycle.
+///
+/// ```
+/// # use competitive::graph::make_weighted_directed_graph;
+/// # use competitive::graph::shortest_path::bellman_ford;
+/// # use competitive::inf::MaybeInf::*;
+/// let g = make_weighted_directed_graph(5, &[(0, 1, 2), (1, 2, -1), (2, 1, -1), (2, 3, 0), (4, 0, 1)]);
+/// assert_eq!(bellman_ford(&g, 0), vec![NonInf(0), NegInf, NegInf, NegInf, Inf]);
+/// ```
+pub fn bellman_ford<'a, G>(g: &'a G, start: usize) -> Vec<MaybeInf<G::Weight>>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Ord + Zero + Add<Output = G::Weight>,
+{
+    let n = g.len();
+    let mut dist = vec![Inf; n];
+    dist[start] = NonInf(G::Weight::zero());
+
+    // after n - 1 rounds all distances are final unless affected by a negative cycle,
+    // and further n rounds propagate NegInf to every node reachable from it
+    for round in 0..2 * n {
+        let mut updated = false;
+        for u in 0..n {
+            if dist[u] == Inf {
+                continue;
+            }
+            for (v, w) in g.weighted_neighbors(u) {
+                let nd = dist[u].clone() + w;
+                if nd < dist[v] {
+                    dist[v] = if round + 1 >= n { NegInf } else { nd };
+                    updated = true;
+                }
+            }
+        }
+        if !updated {
+            break;
+        }
+    }
+    dist
+}
+
+/// O(VE) in the worst case, but usually much faster.
+/// Shortest paths from `start` by Shortest Path Faster Algorithm (queue-based Bellman-Ford).
+/// Weights can be negative, and `None` is returned if a negative cycle is reachable from `start`.
+pub fn spfa<'a, G>(g: &'a G, start: usize) -> Option<ShortestPaths<G::Weight>>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Ord + Zero + Add<Output = G::Weight>,
+{
+    let n = g.len();
+    let mut dist = vec![Inf; n];
+    let mut prev = vec![None; n];
+    let mut in_queue = vec![false; n];
+    // number of edges on the current shortest path
+    let mut len = vec![0; n];
+    let mut q = VecDeque::new();
+
+    dist[start] = NonInf(G::Weight::zero());
+    in_queue[start] = true;
+    q.push_back(start);
+
+    while let Some(u) = q.pop_front() {
+        in_queue[u] = false;
+        for (v, w) in g.weighted_neighbors(u) {
+            let nd = dist[u].clone() + w;
+            if nd < dist[v] {
+                dist[v] = nd;
+                prev[v] = Some(u);
+                len[v] = len[u] + 1;
+                if len[v] >= n {
+                    return None;
+                }
+                if !in_queue[v] {
+                    in_queue[v] = true;
+                    q.push_back(v);
+                }
+            }
+        }
+    }
+
+    Some(ShortestPaths { dist, prev })
+}
+
+/// O(VE).
+/// Find a negative cycle anywhere in the graph.
+/// Returns nodes `[v_0, v_1, ..., v_{k-1}]` of the cycle such that `v_i -> v_{i+1}` and `v_{k-1} -> v_0` are edges.
+pub fn find_negative_cycle<'a, G>(g: &'a G) -> Option<Vec<usize>>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Ord + Zero + Add<Output = G::Weight>,
+{
+    let n = g.len();
+    // as if a virtual source has edges of weight 0 to all nodes
+    let mut dist = vec![G::Weight::zero(); n];
+    let mut prev = vec![None; n];
+
+    let mut last = None;
+    for _ in 0..n {
+        last = None;
+        for u in 0..n {
+            for (v, w) in g.weighted_neighbors(u) {
+                let nd = dist[u].clone() + w;
+                if nd < dist[v] {
+                    dist[v] = nd;
+                    prev[v] = Some(u);
+                    last = Some(v);
+                }
+            }
+        }
+        last?;
+    }
+
+    // a node updated in the n-th round leads back to a negative cycle through prev
+    let mut v = last?;
+    for _ in 0..n {
+        v = prev[v].unwrap();
+    }
+
+    let mut ret = vec![v];
+    let mut u = prev[v].unwrap();
+    while u != v {
+        ret.push(u);
+        u = prev[u].unwrap();
+    }
+    ret.reverse();
+    Some(ret)
+}
+
+#[test]
+fn bellman_ford_test() {
+    use super::make_weighted_directed_graph;
+
+    // no negative cycle
+    let n = 25;
+    let edges = (0..n * 3)
+        .map(|i| (i * 7 % n, (i * 11 + 3) % n, (i * 37 % 13) as i64 - 2))
+        .filter(|&(u, v, _)| u < v)
+        .collect::<Vec<_>>();
+    let g = make_weighted_directed_graph(n, &edges);
+    let shifted = edges
+        .iter()
+        .map(|&(u, v, w)| (u, v, w + 2))
+        .collect::<Vec<_>>();
+    assert!(find_negative_cycle(&g).is_none());
+
+    for s in 0..n {
+        let dist = bellman_ford(&g, s);
+        let sp = spfa(&g, s).unwrap();
+        assert_eq!(sp.dist, dist);
+        for (v, &d) in dist.iter().enumerate() {
+            if let Some(path) = sp.path_to(v) {
+                let w = path
+                    .windows(2)
+                    .map(|e| {
+                        edges
+                            .iter()
+                            .filter(|&&(a, b, _)| a == e[0] && b == e[1])
+                            .map(|&(_, _, w)| w)
+                            .min()
+                            .unwrap()
+                    })
+                    .sum::<i64>();
+                assert_eq!(d, NonInf(w));
+            } else {
+                assert_eq!(d, Inf);
+            }
+        }
+
+        let gs = make_weighted_directed_graph(n, &shifted);
+        assert_eq!(
+            bellman_ford(&gs, s),
+            dijkstra(&gs, s).dist,
+            "non-negative weights"
+        );
+    }
+
+    // 0 -> 1 -> 2 -> 3 -> 1 is a negative cycle, 4 is reachable from it, 5 is not reachable
+    let g = make_weighted_directed_graph(
+        6,
+        &[
+            (0, 1, 1),
+            (1, 2, 2),
+            (2, 3, -4),
+            (3, 1, 1),
+            (3, 4, 10),
+            (5, 0, 3),
+        ],
+    );
+    assert_eq!(
+        bellman_ford(&g, 0),
+        vec![NonInf(0), NegInf, NegInf, NegInf, NegInf, Inf]
+    );
+    assert_eq!(
+        bellman_ford(&g, 4),
+        vec![Inf, Inf, Inf, Inf, NonInf(0), Inf]
+    );
+    assert!(spfa(&g, 0).is_none());
+    assert!(spfa(&g, 4).is_some());
+
+    let mut cycle = find_negative_cycle(&g).unwrap();
+    let k = cycle.iter().position(|&v| v == 1).unwrap();
+    cycle.rotate_left(k);
+    assert_eq!(cycle, vec![1, 2, 3]);
+
+    // self loop
+    let g = make_weighted_directed_graph(2, &[(0, 1, 1), (1, 1, -1)]);
+    assert_eq!(find_negative_cycle(&g), Some(vec![1]));
+    assert_eq!(bellman_ford(&g, 0), vec![NonInf(0), NegInf]);
+}
+
+/// Result of all-pairs shortest path algorithms
+#[derive(Clone, Debug)]
+pub struct AllPairsShortestPaths<W> {
+    /// `dist[u][v]` is the distance from `u` to `v`,
+    /// `Inf` if unreachable and `NegInf` if a negative cycle lies on a path from `u` to `v`
+    pub dist: Vec<Vec<MaybeInf<W>>>,
+    /// `next[u][v]` is the node next to `u` on a shortest path from `u` to `v`
+    pub next: Vec<Vec<Option<usize>>>,
+}
+
+impl<W> AllPairsShortestPaths<W>
+where
+    W: Clone + Ord + Zero + Add<Output = W>,
+{
+    /// O(V^3).
+    /// All-pairs shortest paths by Warshall-Floyd algorithm on an adjacency matrix.
+    /// `mat[u][v]` is the weight of the edge from `u` to `v`, or `Inf` if there is no edge.
+    pub fn from_matrix(mat: &[Vec<MaybeInf<W>>]) -> Self {
+        let n = mat.len();
+        let mut dist = vec![vec![Inf; n]; n];
+        let mut next = vec![vec![None; n]; n];
+        for u in 0..n {
+            for (v, w) in mat[u].iter().enumerate() {
+                if *w < dist[u][v] {
+                    dist[u][v] = w.clone();
+                    next[u][v] = Some(v);
+                }
+            }
+            if NonInf(W::zero()) < dist[u][u] {
+                dist[u][u] = NonInf(W::zero());
+                next[u][u] = Some(u);
+            }
+        }
+
+        let mut ret = Self { dist, next };
+        ret.run();
+        ret
+    }
+
+    /// O(V^3 + E).
+    /// All-pairs shortest paths by Warshall-Floyd algorithm.
+    pub fn new<'a, G>(g: &'a G) -> Self
+    where
+        G: WGraph<'a, NodeId = usize, Weight = W>,
+    {
+        let n = g.len();
+        let mut mat = vec![vec![Inf; n]; n];
+        for (u, row) in mat.iter_mut().enumerate() {
+            for (v, w) in g.weighted_neighbors(u) {
+                row[v] = row[v].clone().min(NonInf(w));
+            }
+        }
+        Self::from_matrix(&mat)
+    }
+
+    fn run(&mut self) {
+        let n = self.dist.len();
+        let zero = NonInf(W::zero());
+
+        for k in 0..n {
+            // k is on a negative cycle through nodes less than or equal to k.
+            // Making it NegInf here keeps finite values from diverging.
+            if self.dist[k][k] < zero {
+                self.dist[k][k] = NegInf;
+            }
+            for i in 0..n {
+                if self.dist[i][k] == Inf {
+                    continue;
+                }
+                for j in 0..n {
+                    if self.dist[k][j] == Inf {
+                        continue;
+                    }
+                    let nd = self.dist[i][k].clone() + self.dist[k][j].clone();
+                    if nd < self.dist[i][j] {
+                        self.dist[i][j] = nd;
+                        self.next[i][j] = self.next[i][k];
+                    }
+                }
+            }
+        }
+
+        for k in 0..n {
+            if self.dist[k][k] >= zero {
+                continue;
+            }
+            for i in 0..n {
+                if self.dist[i][k] == Inf {
+                    continue;
+                }
+                for j in 0..n {
+                    if self.dist[k][j] != Inf {
+                        self.dist[i][j] = NegInf;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns true if the graph has a negative cycle.
+    pub fn has_negative_cycle(&self) -> bool {
+        (0..self.dist.len()).any(|v| self.dist[v][v] == NegInf)
+    }
+
+    /// O(length of path).
+    /// Nodes on a shortest path from `u` to `v`, or `None` if the distance is not finite.
+    pub fn path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+        if !matches!(self.dist[u][v], NonInf(_)) {
+            return None;
+        }
+        let mut ret = vec![u];
+        let mut cur = u;
+        while cur != v {
+            cur = self.next[cur][v].unwrap();
+            ret.push(cur);
+        }
+        Some(ret)
+    }
+
+    /// O(V^2).
+    /// Update distances when an edge from `u` to `v` of weight `w` is added.
+    pub fn add_edge(&mut self, u: usize, v: usize, w: W) {
+        let n = self.dist.len();
+        let zero = NonInf(W::zero());
+
+        // the new edge closes a negative cycle
+        let cycle = self.dist[v][u] != Inf && self.dist[v][u].clone() + w.clone() < zero;
+
+        let from = (0..n)
+            .filter(|&i| self.dist[i][u] != Inf)
+            .map(|i| (i, self.dist[i][u].clone()))
+            .collect::<Vec<_>>();
+        let to = (0..n)
+            .filter(|&j| self.dist[v][j] != Inf)
+            .map(|j| (j, self.dist[v][j].clone()))
+            .collect::<Vec<_>>();
+
+        for (i, diu) in from {
+            let first = if i == u { Some(v) } else { self.next[i][u] };
+            for (j, dvj) in to.iter().cloned() {
+                let nd = if cycle {
+                    NegInf
+                } else {
+                    diu.clone() + w.clone() + dvj
+                };
+                if nd < self.dist[i][j] {
+                    self.dist[i][j] = nd;
+                    self.next[i][j] = first;
+                }
+            }
+        }
+    }
+}
+
+/// O(V^3 + E).
+/// All-pairs shortest paths by Warshall-Floyd algorithm. Weights can be negative.
+///
+/// ```
+/// # use competitive::graph::make_weighted_directed_graph;
+/// # use competitive::graph::shortest_path::warshall_floyd;
+/// # use competitive::inf::MaybeInf::*;
+/// let g = make_weighted_directed_graph(3, &[(0, 1, 4), (1, 2, -2), (0, 2, 3)]);
+/// let apsp = warshall_floyd(&g);
+/// assert_eq!(apsp.dist[0], vec![NonInf(0), NonInf(4), NonInf(2)]);
+/// assert_eq!(apsp.dist[2][0], Inf);
+/// assert_eq!(apsp.path(0, 2), Some(vec![0, 1, 2]));
+/// ```
+pub fn warshall_floyd<'a, G>(g: &'a G) -> AllPairsShortestPaths<G::Weight>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Ord + Zero + Add<Output = G::Weight>,
+{
+    AllPairsShortestPaths::new(g)
+}
+
+/// O(VE log V).
+/// All-pairs shortest distances by Johnson's algorithm. Weights can be negative.
+/// Returns `None` if the graph has a negative cycle.
+pub fn johnson<'a, G>(g: &'a G) -> Option<Vec<Vec<MaybeInf<G::Weight>>>>
+where
+    G: WGraph<'a, NodeId = usize>,
+    G::Weight: Clone + Ord + Zero + Add<Output = G::Weight> + Sub<Output = G::Weight>,
+{
+    let n = g.len();
+
+    // potential by Bellman-Ford from a virtual source with edges of weight 0 to all nodes
+    let mut h = vec![G::Weight::zero(); n];
+    for round in 0..=n {
+        let mut updated = false;
+        for u in 0..n {
+            for (v, w) in g.weighted_neighbors(u) {
+                let nd = h[u].clone() + w;
+                if nd < h[v] {
+                    h[v] = nd;
+                    updated = true;
+                }
+            }
+        }
+        if !updated {
+            break;
+        }
+        if round == n {
+            return None;
+        }
+    }
+
+    // reweighted edges are non-negative
+    let rg = (0..n)
+        .map(|u| {
+            g.weighted_neighbors(u)
+                .map(|(v, w)| (v, w + h[u].clone() - h[v].clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let ret = (0..n)
+        .map(|s| {
+            dijkstra(&rg, s)
+                .dist
+                .into_iter()
+                .zip(h.iter())
+                .map(|(d, hv)| d + hv.clone() - h[s].clone())
+                .collect()
+        })
+        .collect();
+    Some(ret)
+}
+
+#[test]
+fn all_pairs_shortest_paths_test() {
+    use super::make_weighted_directed_graph;
+
+    let check_paths = |apsp: &AllPairsShortestPaths<i64>, edges: &[(usize, usize, i64)]| {
+        let n = apsp.dist.len();
+        for u in 0..n {
+            for v in 0..n {
+                match apsp.path(u, v) {
+                    Some(path) => {
+                        assert_eq!((path[0], *path.last().unwrap()), (u, v));
+                        let w = path
+                            .windows(2)
+                            .map(|e| {
+                                edges
+                                    .iter()
+                                    .filter(|&&(a, b, _)| (a, b) == (e[0], e[1]))
+                                    .map(|&(_, _, w)| w)
+                                    .min()
+                                    .unwrap()
+                            })
+                            .sum::<i64>();
+                        assert_eq!(apsp.dist[u][v], NonInf(w));
+                    }
+                    None => assert!(!matches!(apsp.dist[u][v], NonInf(_))),
+                }
+            }
+        }
+    };
+
+    for seed in 0..30 {
+        let n = 12;
+        let edges = (0..n * 2)
+            .map(|i| {
+                let w = ((i * 31 + seed * 7) % 23) as i64 - 3;
+                ((i * 5 + seed) % n, (i * 7 + seed * 3 + 1) % n, w)
+            })
+            .collect::<Vec<_>>();
+        let g = make_weighted_directed_graph(n, &edges);
+
+        let apsp = warshall_floyd(&g);
+        for s in 0..n {
+            assert_eq!(apsp.dist[s], bellman_ford(&g, s));
+        }
+        assert_eq!(apsp.has_negative_cycle(), find_negative_cycle(&g).is_some());
+        check_paths(&apsp, &edges);
+
+        match johnson(&g) {
+            Some(dist) => assert_eq!(dist, apsp.dist),
+            None => assert!(apsp.has_negative_cycle()),
+        }
+
+        // add edges one by one
+        let mut inc = AllPairsShortestPaths::from_matrix(&vec![vec![Inf; n]; n]);
+        for (k, &(u, v, w)) in edges.iter().enumerate() {
+            inc.add_edge(u, v, w);
+            let g = make_weighted_directed_graph(n, &edges[..=k]);
+            assert_eq!(inc.dist, warshall_floyd(&g).dist);
+            check_paths(&inc, &edges[..=k]);
+        }
+    }
+}
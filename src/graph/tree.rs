@@ -0,0 +1,377 @@
+use super::Graph;
+
+/// Rooted tree with precomputed parent, depth, subtree size, pre-order times and binary lifting table
+///
+/// All computations are done without recursion.
+///
+/// ```
+/// # use competitive::graph::make_undirected_graph;
+/// # use competitive::graph::tree::RootedTree;
+/// //     0
+/// //    / \
+/// //   1   2
+/// //  / \
+/// // 3   4
+/// let g = make_undirected_graph(5, &[(0, 1), (0, 2), (1, 3), (1, 4)]);
+/// let t = RootedTree::new(&g, 0);
+/// assert_eq!(t.parent(3), Some(1));
+/// assert_eq!(t.depth(4), 2);
+/// assert_eq!(t.size(1), 3);
+/// assert_eq!(t.lca(3, 4), 1);
+/// assert_eq!(t.dist(3, 2), 3);
+/// assert_eq!(t.path(3, 2), vec![3, 1, 0, 2]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RootedTree {
+    root: usize,
+    parent: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    tin: Vec<usize>,
+    order: Vec<usize>,
+    // up[k][v]: 2^k-th ancestor of v, or root if it does not exist
+    up: Vec<Vec<usize>>,
+}
+
+impl RootedTree {
+    /// O(n log n).
+    /// Construct from an undirected tree `g` rooted at `root`.
+    pub fn new<'a, G: Graph<'a, NodeId = usize>>(g: &'a G, root: usize) -> Self {
+        let n = g.len();
+        let mut parent = vec![None; n];
+        let mut children = vec![vec![]; n];
+        let mut visited = vec![false; n];
+        let mut stack = vec![root];
+        visited[root] = true;
+        while let Some(u) = stack.pop() {
+            for v in g.neighbors(u) {
+                if !visited[v] {
+                    visited[v] = true;
+                    parent[v] = Some(u);
+                    children[u].push(v);
+                    stack.push(v);
+                }
+            }
+        }
+        assert!(visited.iter().all(|&b| b), "graph is not connected");
+        Self::build(root, parent, children)
+    }
+
+    /// O(n log n).
+    /// Construct from the parent of each node. `parent[root]` is ignored.
+    pub fn from_parents(parent: &[usize], root: usize) -> Self {
+        let n = parent.len();
+        let parent = (0..n)
+            .map(|v| if v == root { None } else { Some(parent[v]) })
+            .collect::<Vec<_>>();
+        let mut children = vec![vec![]; n];
+        for (v, p) in parent.iter().enumerate() {
+            if let Some(p) = *p {
+                children[p].push(v);
+            }
+        }
+        Self::build(root, parent, children)
+    }
+
+    fn build(root: usize, parent: Vec<Option<usize>>, children: Vec<Vec<usize>>) -> Self {
+        let n = parent.len();
+
+        // pre-order, visiting children in the given order
+        let mut order = Vec::with_capacity(n);
+        let mut depth = vec![0; n];
+        let mut stack = vec![root];
+        while let Some(u) = stack.pop() {
+            order.push(u);
+            for &v in children[u].iter().rev() {
+                depth[v] = depth[u] + 1;
+                stack.push(v);
+            }
+        }
+        assert_eq!(order.len(), n, "not a tree");
+
+        let mut tin = vec![0; n];
+        for (i, &v) in order.iter().enumerate() {
+            tin[v] = i;
+        }
+        let mut size = vec![1; n];
+        for &v in order.iter().rev() {
+            if let Some(p) = parent[v] {
+                size[p] += size[v];
+            }
+        }
+
+        let log = (usize::BITS - n.max(1).leading_zeros()) as usize;
+        let mut up = vec![parent.iter().map(|p| p.unwrap_or(root)).collect::<Vec<_>>()];
+        for k in 1..log.max(1) {
+            let next = up[k - 1].iter().map(|&v| up[k - 1][v]).collect();
+            up.push(next);
+        }
+
+        Self {
+            root,
+            parent,
+            children,
+            depth,
+            size,
+            tin,
+            order,
+            up,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Parent of `v`, or `None` for the root
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parent[v]
+    }
+
+    pub fn children(&self, v: usize) -> &[usize] {
+        &self.children[v]
+    }
+
+    /// Number of edges between the root and `v`
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// Number of nodes in the subtree of `v`
+    pub fn size(&self, v: usize) -> usize {
+        self.size[v]
+    }
+
+    /// Nodes in pre-order
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Position of `v` in pre-order.
+    /// The subtree of `v` occupies `tin(v)..tout(v)`.
+    pub fn tin(&self, v: usize) -> usize {
+        self.tin[v]
+    }
+
+    /// End of the subtree of `v` in pre-order (exclusive)
+    pub fn tout(&self, v: usize) -> usize {
+        self.tin[v] + self.size[v]
+    }
+
+    /// O(1).
+    /// Returns true if `u` is an ancestor of `v`. A node is an ancestor of itself.
+    pub fn is_ancestor(&self, u: usize, v: usize) -> bool {
+        self.tin(u) <= self.tin(v) && self.tin(v) < self.tout(u)
+    }
+
+    /// O(log n).
+    /// `k`-th ancestor of `v`, or `None` if `k` is greater than the depth of `v`.
+    pub fn kth_ancestor(&self, v: usize, k: usize) -> Option<usize> {
+        if k > self.depth[v] {
+            return None;
+        }
+        let mut v = v;
+        for (i, up) in self.up.iter().enumerate() {
+            if k >> i & 1 == 1 {
+                v = up[v];
+            }
+        }
+        Some(v)
+    }
+
+    /// O(log n).
+    /// Lowest common ancestor by binary lifting
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        if self.is_ancestor(u, v) {
+            return u;
+        }
+        let mut u = u;
+        for up in self.up.iter().rev() {
+            if !self.is_ancestor(up[u], v) {
+                u = up[u];
+            }
+        }
+        self.up[0][u]
+    }
+
+    /// O(log n).
+    /// Number of edges between `u` and `v`
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// O(log n).
+    /// `k`-th node on the path from `u` to `v` (`u` itself for `k = 0`),
+    /// or `None` if `k` is greater than the distance.
+    pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let w = self.lca(u, v);
+        let du = self.depth[u] - self.depth[w];
+        let dv = self.depth[v] - self.depth[w];
+        if k <= du {
+            self.kth_ancestor(u, k)
+        } else if k <= du + dv {
+            self.kth_ancestor(v, du + dv - k)
+        } else {
+            None
+        }
+    }
+
+    /// O(length of path + log n).
+    /// Nodes on the path from `u` to `v`
+    pub fn path(&self, u: usize, v: usize) -> Vec<usize> {
+        let w = self.lca(u, v);
+        let mut ret = vec![];
+        let mut x = u;
+        while x != w {
+            ret.push(x);
+            x = self.parent[x].unwrap();
+        }
+        ret.push(w);
+
+        let len = ret.len();
+        let mut x = v;
+        while x != w {
+            ret.push(x);
+            x = self.parent[x].unwrap();
+        }
+        ret[len..].reverse();
+        ret
+    }
+}
+
+/// Lowest common ancestor by Euler tour and sparse table
+///
+/// Construction takes O(n log n), and each query takes O(1).
+pub struct EulerTourLca {
+    // first[v]: first position of v in the Euler tour
+    first: Vec<usize>,
+    // table[k][i]: node of minimum depth in tour[i..i + 2^k]
+    table: Vec<Vec<(usize, usize)>>,
+}
+
+impl EulerTourLca {
+    pub fn new(tree: &RootedTree) -> Self {
+        let n = tree.len();
+        let mut tour = Vec::with_capacity(2 * n);
+        let mut first = vec![0; n];
+
+        let mut stack = vec![(tree.root(), 0)];
+        while let Some((u, i)) = stack.pop() {
+            if i == 0 {
+                first[u] = tour.len();
+            }
+            tour.push((tree.depth(u), u));
+            if let Some(&v) = tree.children(u).get(i) {
+                stack.push((u, i + 1));
+                stack.push((v, 0));
+            }
+        }
+
+        let mut table = vec![tour];
+        let len = table[0].len();
+        let mut k = 1;
+        while 1 << k <= len {
+            let prev = &table[k - 1];
+            let next = (0..=len - (1 << k))
+                .map(|i| prev[i].min(prev[i + (1 << (k - 1))]))
+                .collect();
+            table.push(next);
+            k += 1;
+        }
+
+        Self { first, table }
+    }
+
+    /// O(1).
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (l, r) = if self.first[u] <= self.first[v] {
+            (self.first[u], self.first[v] + 1)
+        } else {
+            (self.first[v], self.first[u] + 1)
+        };
+        let k = (usize::BITS - 1 - (r - l).leading_zeros()) as usize;
+        self.table[k][l].min(self.table[k][r - (1 << k)]).1
+    }
+}
+
+#[test]
+fn rooted_tree_test() {
+    use super::make_undirected_graph;
+
+    for seed in 0..20 {
+        let n = 1 + seed * 3;
+        let par = (0..n)
+            .map(|v| if v == 0 { 0 } else { (v * 7 + seed) % v })
+            .collect::<Vec<_>>();
+        let root = seed % n;
+        let edges = (1..n).map(|v| (par[v], v)).collect::<Vec<_>>();
+        let g = make_undirected_graph(n, &edges);
+        let t = RootedTree::new(&g, root);
+        let et = EulerTourLca::new(&t);
+
+        // naive ancestors
+        let anc = |v: usize| {
+            let mut ret = vec![v];
+            while let Some(p) = t.parent(*ret.last().unwrap()) {
+                ret.push(p);
+            }
+            ret
+        };
+
+        for v in 0..n {
+            let a = anc(v);
+            assert_eq!(t.depth(v), a.len() - 1);
+            assert_eq!(*a.last().unwrap(), root);
+            assert_eq!(t.size(v), (0..n).filter(|&u| anc(u).contains(&v)).count());
+            assert_eq!(t.order()[t.tin(v)], v);
+            for k in 0..n + 2 {
+                assert_eq!(t.kth_ancestor(v, k), a.get(k).cloned());
+            }
+            for u in 0..n {
+                assert_eq!(t.is_ancestor(u, v), a.contains(&u));
+
+                let b = anc(u);
+                let w = *a.iter().find(|x| b.contains(x)).unwrap();
+                assert_eq!(t.lca(u, v), w);
+                assert_eq!(et.lca(u, v), w);
+
+                let path = t.path(u, v);
+                assert_eq!(path.len(), t.dist(u, v) + 1);
+                assert_eq!((path[0], *path.last().unwrap()), (u, v));
+                assert!(path
+                    .windows(2)
+                    .all(|e| par[e[0]] == e[1] || par[e[1]] == e[0]));
+                for k in 0..path.len() + 2 {
+                    assert_eq!(t.jump(u, v, k), path.get(k).cloned());
+                }
+            }
+        }
+
+        let t2 = RootedTree::from_parents(&par, 0);
+        let t0 = RootedTree::new(&g, 0);
+        for v in 0..n {
+            assert_eq!(t2.parent(v), t0.parent(v));
+            assert_eq!(t2.depth(v), t0.depth(v));
+            assert_eq!(t2.size(v), t0.size(v));
+        }
+    }
+
+    // long path
+    let n = 1_000_000;
+    let par = (0..n).map(|v| v.max(1) - 1).collect::<Vec<_>>();
+    let t = RootedTree::from_parents(&par, 0);
+    let et = EulerTourLca::new(&t);
+    assert_eq!(t.depth(n - 1), n - 1);
+    assert_eq!(t.lca(n - 1, n / 2), n / 2);
+    assert_eq!(et.lca(n - 1, n / 2), n / 2);
+    assert_eq!(t.jump(0, n - 1, 12345), Some(12345));
+}
@@ -0,0 +1,277 @@
+use std::ops::RangeInclusive;
+
+/// O(1).
+/// Floor of square root of `n`. Exact for all `u64`.
+pub fn isqrt(n: u64) -> u64 {
+    let mut x = (n as f64).sqrt() as u64;
+    while x.checked_mul(x).is_none_or(|s| s > n) {
+        x -= 1;
+    }
+    while (x + 1).checked_mul(x + 1).is_some_and(|s| s <= n) {
+        x += 1;
+    }
+    x
+}
+
+#[test]
+fn isqrt_test() {
+    for n in 0..10000 {
+        let x = isqrt(n);
+        assert!(x * x <= n && n < (x + 1) * (x + 1));
+    }
+    assert_eq!(isqrt(u64::MAX), 4294967295);
+    assert_eq!(isqrt(999_999_999_999_999_999), 999_999_999);
+    assert_eq!(isqrt(1_000_000_000_000_000_000), 1_000_000_000);
+    assert_eq!(isqrt(4294967295 * 4294967295), 4294967295);
+    assert_eq!(isqrt(4294967295 * 4294967295 - 1), 4294967294);
+}
+
+/// O(k).
+/// Floor of `k`-th root of `n`. Exact for all `u64`.
+pub fn iroot(n: u64, k: u32) -> u64 {
+    assert!(k > 0);
+    if k == 1 || n <= 1 {
+        return n;
+    }
+
+    let mut x = (n as f64).powf(1.0 / k as f64) as u64;
+    while x.checked_pow(k).is_none_or(|s| s > n) {
+        x -= 1;
+    }
+    while (x + 1).checked_pow(k).is_some_and(|s| s <= n) {
+        x += 1;
+    }
+    x
+}
+
+#[test]
+fn iroot_test() {
+    for k in 1..10 {
+        for n in 0..3000 {
+            let x = iroot(n, k);
+            assert!(x.pow(k) <= n && n < (x + 1).pow(k));
+        }
+    }
+    assert_eq!(iroot(u64::MAX, 2), 4294967295);
+    assert_eq!(iroot(u64::MAX, 3), 2642245);
+    assert_eq!(iroot(u64::MAX, 64), 1);
+    assert_eq!(iroot(1_000_000_000_000_000_000, 3), 1_000_000);
+    assert_eq!(iroot(999_999_999_999_999_999, 3), 999_999);
+}
+
+/// O(1).
+/// Floor of `a / b`. Correct for negative values.
+pub fn floor_div(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+/// O(1).
+/// Ceil of `a / b`. Correct for negative values.
+pub fn ceil_div(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) == (b < 0) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+#[test]
+fn floor_ceil_div_test() {
+    for a in -20..=20 {
+        for b in (-5..=5).filter(|&b| b != 0) {
+            let x = a as f64 / b as f64;
+            assert_eq!(floor_div(a, b), x.floor() as i64);
+            assert_eq!(ceil_div(a, b), x.ceil() as i64);
+        }
+    }
+    assert_eq!(floor_div(i64::MIN, 1), i64::MIN);
+    assert_eq!(ceil_div(i64::MAX, 2), 1 << 62);
+}
+
+fn floor_sum_unsigned(n: u128, m: u128, a: u128, b: u128) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+
+    let (mut n, mut m, mut a, mut b) = (n, m, a, b);
+    let mut ret = 0;
+    loop {
+        if a >= m {
+            ret += n * (n - 1) / 2 * (a / m);
+            a %= m;
+        }
+        if b >= m {
+            ret += n * (b / m);
+            b %= m;
+        }
+
+        let y_max = a * n + b;
+        if y_max < m {
+            break;
+        }
+        n = y_max / m;
+        b = y_max % m;
+        std::mem::swap(&mut m, &mut a);
+    }
+    ret
+}
+
+/// O(log m).
+/// Returns `sum_{i=0}^{n-1} floor((a * i + b) / m)`.
+///
+/// `a` and `b` can be negative, but `n >= 0` and `m > 0` are required.
+pub fn floor_sum(n: i64, m: i64, a: i64, b: i64) -> i64 {
+    assert!(n >= 0 && m > 0);
+    let (n, m, a, b) = (n as i128, m as i128, a as i128, b as i128);
+
+    let mut ret = 0;
+    let mut a = a;
+    let mut b = b;
+    if a < 0 {
+        let a2 = a.rem_euclid(m);
+        ret -= n * (n - 1) / 2 * ((a2 - a) / m);
+        a = a2;
+    }
+    if b < 0 {
+        let b2 = b.rem_euclid(m);
+        ret -= n * ((b2 - b) / m);
+        b = b2;
+    }
+
+    (ret + floor_sum_unsigned(n as u128, m as u128, a as u128, b as u128) as i128) as i64
+}
+
+#[test]
+fn floor_sum_test() {
+    for n in 0..20 {
+        for m in 1..20 {
+            for a in -20..20 {
+                for b in -20..20 {
+                    let expected = (0..n).map(|i| floor_div(a * i + b, m)).sum::<i64>();
+                    assert_eq!(floor_sum(n, m, a, b), expected);
+                }
+            }
+        }
+    }
+    assert_eq!(floor_sum(1_000_000_000, 1, 1, 0), 499_999_999_500_000_000);
+    assert_eq!(
+        floor_sum(1_000_000_000, 1_000_000_000, 1_000_000_000, -1),
+        499_999_998_500_000_000
+    );
+}
+
+/// Iterator over blocks of `i` in `1..=n` with the same `n / i`
+pub struct QuotientBlocks {
+    n: u64,
+    // remaining blocks cover `i..=j`
+    i: u64,
+    j: u64,
+}
+
+impl Iterator for QuotientBlocks {
+    type Item = (u64, RangeInclusive<u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i > self.j {
+            return None;
+        }
+        let q = self.n / self.i;
+        let r = (self.n / q).min(self.j);
+        let ret = (q, self.i..=r);
+        if r == self.j {
+            self.i = 1;
+            self.j = 0;
+        } else {
+            self.i = r + 1;
+        }
+        Some(ret)
+    }
+}
+
+impl DoubleEndedIterator for QuotientBlocks {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.i > self.j {
+            return None;
+        }
+        let q = self.n / self.j;
+        let l = q.checked_add(1).map_or(1, |q1| self.n / q1 + 1).max(self.i);
+        let ret = (q, l..=self.j);
+        if l == self.i {
+            self.i = 1;
+            self.j = 0;
+        } else {
+            self.j = l - 1;
+        }
+        Some(ret)
+    }
+}
+
+/// O(sqrt n) in total.
+/// Returns an iterator over `(q, l..=r)` such that `n / i == q` for all `i` in `l..=r`.
+/// Blocks cover `1..=n` in increasing order of `i`, and can be iterated in reverse as well.
+/// Ranges are inclusive rather than `l..r` so that the last block stays representable for `n == u64::MAX`.
+///
+/// ```
+/// # use competitive::integer::quotient_blocks;
+/// let v = quotient_blocks(10).collect::<Vec<_>>();
+/// assert_eq!(v, vec![(10, 1..=1), (5, 2..=2), (3, 3..=3), (2, 4..=5), (1, 6..=10)]);
+/// ```
+pub fn quotient_blocks(n: u64) -> QuotientBlocks {
+    QuotientBlocks { n, i: 1, j: n }
+}
+
+#[test]
+fn quotient_blocks_test() {
+    for n in 0..200 {
+        let mut i = 1;
+        let v = quotient_blocks(n).collect::<Vec<_>>();
+        for (q, r) in &v {
+            assert_eq!(*r.start(), i);
+            assert!(r.start() <= r.end());
+            for j in r.clone() {
+                assert_eq!(n / j, *q);
+            }
+            i = r.end() + 1;
+        }
+        assert_eq!(i, n + 1);
+
+        let mut rev = quotient_blocks(n).rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, v);
+
+        // mixed iteration from both ends
+        let mut it = quotient_blocks(n);
+        let mut front = vec![];
+        let mut back = vec![];
+        for k in 0.. {
+            let x = if k % 3 == 0 {
+                it.next_back()
+            } else {
+                it.next()
+            };
+            match x {
+                Some(x) if k % 3 == 0 => back.push(x),
+                Some(x) => front.push(x),
+                None => break,
+            }
+        }
+        front.extend(back.into_iter().rev());
+        assert_eq!(front, v);
+    }
+
+    let n = 1_000_000_000_000;
+    assert_eq!(quotient_blocks(n).count() as u64, 2 * isqrt(n) - 1);
+
+    let n = u64::MAX;
+    let mut it = quotient_blocks(n);
+    assert_eq!(it.next(), Some((n, 1..=1)));
+    assert_eq!(it.next(), Some((n / 2, 2..=2)));
+    assert_eq!(it.next_back(), Some((1, n / 2 + 1..=n)));
+    assert_eq!(it.next_back(), Some((2, n / 3 + 1..=n / 2)));
+}
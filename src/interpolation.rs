@@ -0,0 +1,325 @@
+use crate::gf::GF;
+use crate::number::gen_fact_table;
+use crate::poly::{convolution, poly_derivative, poly_div_rem, poly_eval};
+
+/// O(n).
+/// Generate inverse factorial table `1 / i!` for `i = 0, 1, ..., n - 1`
+///
+/// `n` must be at most `P`.
+pub fn gen_inv_fact_table<const P: u64>(n: usize) -> Vec<GF<P>> {
+    if n == 0 {
+        return vec![];
+    }
+    let fact = gen_fact_table::<GF<P>>(n);
+    let mut inv_fact = vec![GF::<P>::new(1); n];
+    inv_fact[n - 1] = fact[n - 1].recip();
+    for i in (1..n).rev() {
+        inv_fact[i - 1] = inv_fact[i] * i;
+    }
+    inv_fact
+}
+
+/// O(n).
+/// Evaluate at `x` the polynomial of degree less than `n` passing through `(i, y[i])` for `i = 0, 1, ..., n - 1`.
+///
+/// `inv_fact[i]` must be `1 / i!` for `i < n`, e.g. `gen_inv_fact_table(n)`.
+/// `n` must be less than `P`.
+pub fn lagrange_consecutive<const P: u64>(y: &[GF<P>], x: u64, inv_fact: &[GF<P>]) -> GF<P> {
+    let n = y.len();
+    if (x as u128) < n as u128 {
+        return y[x as usize];
+    }
+    assert!((n as u64) < P);
+    assert!(inv_fact.len() >= n);
+
+    let x = GF::<P>::new(x % P);
+
+    // left[i] = (x - 0) ... (x - (i - 1)), right[i] = (x - (i + 1)) ... (x - (n - 1))
+    let mut left = vec![GF::<P>::new(1); n + 1];
+    for i in 0..n {
+        left[i + 1] = left[i] * (x - i);
+    }
+    let mut right = vec![GF::<P>::new(1); n + 1];
+    for i in (0..n).rev() {
+        right[i] = right[i + 1] * (x - i);
+    }
+
+    let mut ret = GF::<P>::new(0);
+    for (i, &yi) in y.iter().enumerate() {
+        let t = yi * left[i] * right[i + 1] * inv_fact[i] * inv_fact[n - 1 - i];
+        if (n - 1 - i).is_multiple_of(2) {
+            ret += t;
+        } else {
+            ret -= t;
+        }
+    }
+    ret
+}
+
+#[test]
+fn lagrange_consecutive_test() {
+    type GF = crate::gf::GF<1000000007>;
+
+    // f(x) = x^3 - 2x + 5
+    let f = |x: u64| {
+        let x = GF::new(x % 1000000007);
+        x * x * x - x * 2 + 5
+    };
+    let y = (0..4).map(f).collect::<Vec<_>>();
+    let inv_fact = gen_inv_fact_table(10);
+    for &x in &[0, 3, 4, 100, 1_000_000_000_000_000_000] {
+        assert_eq!(lagrange_consecutive(&y, x, &inv_fact), f(x));
+    }
+
+    assert_eq!(
+        lagrange_consecutive(&[GF::new(7)], 12345, &inv_fact),
+        GF::new(7)
+    );
+    assert_eq!(lagrange_consecutive(&[], 12345, &inv_fact), GF::new(0));
+
+    assert!(gen_inv_fact_table::<1000000007>(0).is_empty());
+    let inv_fact = gen_inv_fact_table::<1000000007>(6);
+    assert_eq!(inv_fact[0], GF::new(1));
+    assert_eq!(inv_fact[5] * 120, GF::new(1));
+}
+
+/// O(n^2).
+/// Evaluate at `x` the polynomial of degree less than `n` passing through `(xs[i], ys[i])`.
+///
+/// `xs` must be pairwise distinct.
+pub fn lagrange<const P: u64>(xs: &[GF<P>], ys: &[GF<P>], x: GF<P>) -> GF<P> {
+    assert_eq!(xs.len(), ys.len());
+
+    if let Some(i) = xs.iter().position(|&xi| xi == x) {
+        return ys[i];
+    }
+
+    let all = xs.iter().fold(GF::<P>::new(1), |acc, &xi| acc * (x - xi));
+    let mut ret = GF::<P>::new(0);
+    for (i, (&xi, &yi)) in xs.iter().zip(ys.iter()).enumerate() {
+        let denom = xs
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold(x - xi, |acc, (_, &xj)| acc * (xi - xj));
+        ret += yi * all / denom;
+    }
+    ret
+}
+
+/// O(n^2).
+/// Coefficients of the polynomial of degree less than `n` passing through `(xs[i], ys[i])`.
+///
+/// `xs` must be pairwise distinct.
+pub fn interpolate_naive<const P: u64>(xs: &[GF<P>], ys: &[GF<P>]) -> Vec<GF<P>> {
+    assert_eq!(xs.len(), ys.len());
+    let n = xs.len();
+
+    // all = (x - xs[0]) ... (x - xs[n - 1])
+    let mut all = vec![GF::<P>::new(1)];
+    for &xi in xs {
+        let mut next = vec![GF::<P>::new(0); all.len() + 1];
+        for (j, &c) in all.iter().enumerate() {
+            next[j + 1] += c;
+            next[j] -= c * xi;
+        }
+        all = next;
+    }
+
+    let mut ret = vec![GF::<P>::new(0); n];
+    for (i, (&xi, &yi)) in xs.iter().zip(ys.iter()).enumerate() {
+        // q = all / (x - xi)
+        let mut q = vec![GF::<P>::new(0); n];
+        let mut carry = GF::<P>::new(0);
+        for j in (0..n).rev() {
+            carry = all[j + 1] + carry * xi;
+            q[j] = carry;
+        }
+
+        let denom = xs
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold(GF::<P>::new(1), |acc, (_, &xj)| acc * (xi - xj));
+        let f = yi / denom;
+        for (r, &c) in ret.iter_mut().zip(q.iter()) {
+            *r += c * f;
+        }
+    }
+    ret
+}
+
+/// Subproduct tree of `(x - xs[0]), ..., (x - xs[n - 1])`.
+/// Node `k` has children `2k + 1` and `2k + 2`, and the root is node `0`.
+struct SubproductTree<const P: u64> {
+    n: usize,
+    tree: Vec<Vec<GF<P>>>,
+}
+
+impl<const P: u64> SubproductTree<P> {
+    fn new(xs: &[GF<P>]) -> Self {
+        let n = xs.len();
+        let mut ret = Self {
+            n,
+            tree: vec![vec![]; 4 * n.max(1)],
+        };
+        if n > 0 {
+            ret.build(0, 0, n, xs);
+        }
+        ret
+    }
+
+    fn build(&mut self, k: usize, l: usize, r: usize, xs: &[GF<P>]) {
+        if r - l == 1 {
+            self.tree[k] = vec![-xs[l], GF::new(1)];
+            return;
+        }
+        let m = (l + r) / 2;
+        self.build(2 * k + 1, l, m, xs);
+        self.build(2 * k + 2, m, r, xs);
+        self.tree[k] = convolution(&self.tree[2 * k + 1], &self.tree[2 * k + 2]);
+    }
+
+    fn evaluate(&self, f: &[GF<P>]) -> Vec<GF<P>> {
+        let mut ret = Vec::with_capacity(self.n);
+        if self.n > 0 {
+            let (_, r) = poly_div_rem(f, &self.tree[0]);
+            self.evaluate_rec(0, 0, self.n, &r, &mut ret);
+        }
+        ret
+    }
+
+    fn evaluate_rec(&self, k: usize, l: usize, r: usize, f: &[GF<P>], ret: &mut Vec<GF<P>>) {
+        if r - l == 1 {
+            ret.push(f.first().cloned().unwrap_or_default());
+            return;
+        }
+        let m = (l + r) / 2;
+        let (_, fl) = poly_div_rem(f, &self.tree[2 * k + 1]);
+        self.evaluate_rec(2 * k + 1, l, m, &fl, ret);
+        let (_, fr) = poly_div_rem(f, &self.tree[2 * k + 2]);
+        self.evaluate_rec(2 * k + 2, m, r, &fr, ret);
+    }
+
+    fn combine(&self, k: usize, l: usize, r: usize, w: &[GF<P>]) -> Vec<GF<P>> {
+        if r - l == 1 {
+            return vec![w[l]];
+        }
+        let m = (l + r) / 2;
+        let a = convolution(&self.combine(2 * k + 1, l, m, w), &self.tree[2 * k + 2]);
+        let b = convolution(&self.combine(2 * k + 2, m, r, w), &self.tree[2 * k + 1]);
+        let mut ret = vec![GF::<P>::new(0); a.len().max(b.len())];
+        for (i, x) in ret.iter_mut().enumerate() {
+            *x = a.get(i).cloned().unwrap_or_default() + b.get(i).cloned().unwrap_or_default();
+        }
+        ret
+    }
+}
+
+/// O(n log^2 n + m log m).
+/// Evaluate polynomial `f` at each point of `xs` (multipoint evaluation by subproduct tree)
+pub fn multipoint_evaluation<const P: u64>(f: &[GF<P>], xs: &[GF<P>]) -> Vec<GF<P>> {
+    if xs.len() <= 32 {
+        return xs.iter().map(|&x| poly_eval(f, x)).collect();
+    }
+    SubproductTree::new(xs).evaluate(f)
+}
+
+#[test]
+fn multipoint_evaluation_test() {
+    type GF = crate::gf::GF<998244353>;
+
+    let f = (0..150_u64)
+        .map(|i| GF::new(i * i * 31 + 17))
+        .collect::<Vec<_>>();
+    let xs = (0..300_u64)
+        .map(|i| GF::new(i * 1234567 + 89))
+        .collect::<Vec<_>>();
+    let expected = xs.iter().map(|&x| poly_eval(&f, x)).collect::<Vec<_>>();
+    assert_eq!(multipoint_evaluation(&f, &xs), expected);
+    assert_eq!(multipoint_evaluation(&f[..3], &xs), {
+        xs.iter()
+            .map(|&x| poly_eval(&f[..3], x))
+            .collect::<Vec<_>>()
+    });
+}
+
+/// O(n log^2 n).
+/// Coefficients of the polynomial of degree less than `n` passing through `(xs[i], ys[i])`.
+///
+/// `xs` must be pairwise distinct.
+pub fn interpolate<const P: u64>(xs: &[GF<P>], ys: &[GF<P>]) -> Vec<GF<P>> {
+    assert_eq!(xs.len(), ys.len());
+    let n = xs.len();
+    if n <= 32 {
+        return interpolate_naive(xs, ys);
+    }
+
+    let tree = SubproductTree::new(xs);
+    let d = tree.evaluate(&poly_derivative(&tree.tree[0]));
+    let w = ys
+        .iter()
+        .zip(d.iter())
+        .map(|(&y, &d)| y / d)
+        .collect::<Vec<_>>();
+
+    let mut ret = tree.combine(0, 0, n, &w);
+    ret.resize(n, GF::new(0));
+    ret
+}
+
+#[test]
+fn interpolate_test() {
+    type GF = crate::gf::GF<998244353>;
+
+    for &n in &[1_u64, 5, 100] {
+        let f = (0..n).map(|i| GF::new(i * 7 + 3)).collect::<Vec<_>>();
+        let xs = (0..n)
+            .map(|i| GF::new(i * i + 2 * i + 5))
+            .collect::<Vec<_>>();
+        let ys = xs.iter().map(|&x| poly_eval(&f, x)).collect::<Vec<_>>();
+        assert_eq!(interpolate(&xs, &ys), f);
+        assert_eq!(interpolate_naive(&xs, &ys), f);
+        assert_eq!(
+            lagrange(&xs, &ys, GF::new(12345)),
+            poly_eval(&f, GF::new(12345))
+        );
+    }
+}
+
+/// O(k log k).
+/// Returns `0^k + 1^k + ... + (n - 1)^k`.
+///
+/// `k + 2` must be less than `P`.
+///
+/// ```
+/// use competitive::interpolation::sum_of_powers;
+/// type GF = competitive::gf::GF<1000000007>;
+///
+/// assert_eq!(sum_of_powers::<1000000007>(11, 2), GF::new(385));
+/// ```
+pub fn sum_of_powers<const P: u64>(n: u64, k: u64) -> GF<P> {
+    let mut y = Vec::with_capacity(k as usize + 2);
+    let mut acc = GF::<P>::new(0);
+    for i in 0..k + 2 {
+        y.push(acc);
+        acc += GF::<P>::new(i).pow(k);
+    }
+    lagrange_consecutive(&y, n, &gen_inv_fact_table(y.len()))
+}
+
+#[test]
+fn sum_of_powers_test() {
+    type GF = crate::gf::GF<1000000007>;
+
+    for k in 0..5 {
+        let mut acc = GF::new(0);
+        for n in 0..20 {
+            assert_eq!(sum_of_powers(n, k), acc);
+            acc += GF::new(n).pow(k);
+        }
+    }
+
+    // 0^0 is treated as 1
+    assert_eq!(sum_of_powers::<1000000007>(1, 0), GF::new(1));
+}
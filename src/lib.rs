@@ -6,16 +6,23 @@ pub mod geo;
 pub mod gf;
 pub mod graph;
 pub mod inf;
+pub mod integer;
+pub mod interpolation;
 pub mod io;
 pub mod iter;
 pub mod ix;
 pub mod kmp;
+pub mod linalg;
+pub mod linear_recurrence;
 pub mod monoid;
+pub mod multiplicative;
 pub mod number;
+pub mod poly;
 pub mod prime;
 pub mod range;
 pub mod segment_tree;
 pub mod slice;
+pub mod two_sat;
 pub mod union_find;
 
 #[macro_use]
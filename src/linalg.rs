@@ -0,0 +1,395 @@
+use crate::gf::GF;
+use num::{Integer, One, Zero};
+use num_rational::Ratio;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A trait of fields
+///
+/// Elements must support the four arithmetic operations, and division by a non-zero element must be exact.
+/// Floating point numbers are treated as an approximation of a field.
+pub trait Field:
+    Clone
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Whether the value should be treated as zero in elimination
+    fn is_negligible(&self) -> bool {
+        self.is_zero()
+    }
+
+    /// Whether `self` is a better pivot than `other`.
+    /// The first non-negligible element is used when this returns `false` always.
+    fn is_better_pivot(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+impl<const P: u64> Field for GF<P> {}
+
+impl<T: Clone + Integer + Neg<Output = T>> Field for Ratio<T> {}
+
+impl Field for f64 {
+    fn is_negligible(&self) -> bool {
+        self.abs() < 1e-9
+    }
+
+    fn is_better_pivot(&self, other: &Self) -> bool {
+        self.abs() > other.abs()
+    }
+}
+
+/// Matrix type used in this module
+pub type Matrix<T> = Vec<Vec<T>>;
+
+fn width<T>(a: &[Vec<T>]) -> usize {
+    a.first().map_or(0, |r| r.len())
+}
+
+/// Find the best pivot row for column `c` from the `r`-th row
+fn find_pivot<T: Field>(a: &[Vec<T>], r: usize, c: usize) -> Option<usize> {
+    let mut p: Option<usize> = None;
+    for (i, ai) in a.iter().enumerate().skip(r) {
+        if !ai[c].is_negligible() && p.is_none_or(|p| ai[c].is_better_pivot(&a[p][c])) {
+            p = Some(i);
+        }
+    }
+    p
+}
+
+/// O(h w min(h, w)).
+/// Transform `a` into the reduced row echelon form in place.
+/// Returns the pivot column of each non-zero row.
+pub fn rref<T: Field>(a: &mut [Vec<T>]) -> Vec<usize> {
+    let h = a.len();
+    let w = width(a);
+    let mut pivots = vec![];
+
+    for c in 0..w {
+        let r = pivots.len();
+        if r == h {
+            break;
+        }
+
+        let p = match find_pivot(a, r, c) {
+            Some(p) => p,
+            None => continue,
+        };
+
+        a.swap(r, p);
+        let inv = T::one() / a[r][c].clone();
+        for x in a[r][c..].iter_mut() {
+            *x = x.clone() * inv.clone();
+        }
+
+        let row = a[r].clone();
+        for (i, ai) in a.iter_mut().enumerate() {
+            if i == r || ai[c].is_negligible() {
+                continue;
+            }
+            let f = ai[c].clone();
+            for (x, y) in ai[c..].iter_mut().zip(row[c..].iter()) {
+                *x = x.clone() - f.clone() * y.clone();
+            }
+        }
+
+        pivots.push(c);
+    }
+
+    pivots
+}
+
+/// O(h w min(h, w)).
+/// Rank of matrix `a`
+pub fn rank<T: Field>(a: &[Vec<T>]) -> usize {
+    rref(&mut a.to_vec()).len()
+}
+
+#[test]
+fn rank_test() {
+    type GF = crate::gf::GF<7>;
+    let a = vec![
+        vec![GF::new(1), GF::new(2), GF::new(3)],
+        vec![GF::new(2), GF::new(4), GF::new(6)],
+        vec![GF::new(0), GF::new(1), GF::new(1)],
+    ];
+    assert_eq!(rank(&a), 2);
+
+    let a = vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]];
+    assert_eq!(rank(&a), 2);
+
+    let a: Matrix<f64> = vec![];
+    assert_eq!(rank(&a), 0);
+}
+
+/// O(n^3).
+/// Determinant of square matrix `a`
+pub fn determinant<T: Field>(a: &[Vec<T>]) -> T {
+    let n = a.len();
+    assert!(a.iter().all(|r| r.len() == n));
+
+    let mut a = a.to_vec();
+    let mut ret = T::one();
+
+    for c in 0..n {
+        let p = match find_pivot(&a, c, c) {
+            Some(p) => p,
+            None => return T::zero(),
+        };
+
+        if p != c {
+            a.swap(c, p);
+            ret = -ret;
+        }
+        ret = ret * a[c][c].clone();
+
+        let inv = T::one() / a[c][c].clone();
+        let (upper, lower) = a.split_at_mut(c + 1);
+        let row = &upper[c];
+        for ai in lower.iter_mut() {
+            let f = ai[c].clone() * inv.clone();
+            for (x, y) in ai[c..].iter_mut().zip(row[c..].iter()) {
+                *x = x.clone() - f.clone() * y.clone();
+            }
+        }
+    }
+
+    ret
+}
+
+#[test]
+fn determinant_test() {
+    let a = vec![
+        vec![Ratio::new(2, 1), Ratio::new(1, 1), Ratio::new(1, 1)],
+        vec![Ratio::new(1, 1), Ratio::new(3, 1), Ratio::new(2, 1)],
+        vec![Ratio::new(1, 1), Ratio::new(0, 1), Ratio::new(0, 1)],
+    ];
+    assert_eq!(determinant(&a), Ratio::new(-1, 1));
+
+    let a = vec![vec![0.0, 2.0], vec![3.0, 4.0]];
+    assert!((determinant(&a) + 6.0).abs() < 1e-9);
+
+    type GF = crate::gf::GF<1000000007>;
+    let a = vec![vec![GF::new(1), GF::new(2)], vec![GF::new(2), GF::new(4)]];
+    assert_eq!(determinant(&a), GF::new(0));
+}
+
+/// O(n^3).
+/// Inverse of square matrix `a`.
+/// Returns `None` if `a` is singular.
+pub fn inverse<T: Field>(a: &[Vec<T>]) -> Option<Matrix<T>> {
+    let n = a.len();
+    assert!(a.iter().all(|r| r.len() == n));
+
+    let mut b = a
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let mut r = r.clone();
+            r.extend((0..n).map(|j| if i == j { T::one() } else { T::zero() }));
+            r
+        })
+        .collect::<Vec<_>>();
+
+    let pivots = rref(&mut b);
+    if pivots.len() < n || pivots[n - 1] >= n {
+        return None;
+    }
+
+    Some(b.into_iter().map(|r| r[n..].to_vec()).collect())
+}
+
+#[test]
+fn inverse_test() {
+    type GF = crate::gf::GF<1000000007>;
+    let a = vec![
+        vec![GF::new(1), GF::new(2), GF::new(3)],
+        vec![GF::new(0), GF::new(1), GF::new(4)],
+        vec![GF::new(5), GF::new(6), GF::new(0)],
+    ];
+    let b = inverse(&a).unwrap();
+    for (i, ai) in a.iter().enumerate() {
+        for j in 0..3 {
+            let x = ai
+                .iter()
+                .zip(&b)
+                .fold(GF::new(0), |acc, (&x, bk)| acc + x * bk[j]);
+            assert_eq!(x, GF::new(if i == j { 1 } else { 0 }));
+        }
+    }
+
+    let a = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+    assert!(inverse(&a).is_none());
+}
+
+fn kernel_of_rref<T: Field>(a: &[Vec<T>], pivots: &[usize], w: usize) -> Matrix<T> {
+    let mut is_pivot = vec![false; w];
+    for &c in pivots {
+        is_pivot[c] = true;
+    }
+
+    let mut ret = vec![];
+    for f in (0..w).filter(|&c| !is_pivot[c]) {
+        let mut x = vec![T::zero(); w];
+        x[f] = T::one();
+        for (r, &c) in pivots.iter().enumerate() {
+            x[c] = -a[r][f].clone();
+        }
+        ret.push(x);
+    }
+    ret
+}
+
+/// O(h w min(h, w)).
+/// Basis of the kernel of `a` (the solutions of `a x = 0`)
+pub fn kernel<T: Field>(a: &[Vec<T>]) -> Matrix<T> {
+    let w = width(a);
+    let mut a = a.to_vec();
+    let pivots = rref(&mut a);
+    kernel_of_rref(&a, &pivots, w)
+}
+
+#[test]
+fn kernel_test() {
+    let a = vec![
+        vec![Ratio::new(1, 1), Ratio::new(2, 1), Ratio::new(3, 1)],
+        vec![Ratio::new(2, 1), Ratio::new(4, 1), Ratio::new(6, 1)],
+    ];
+    let ker = kernel(&a);
+    assert_eq!(ker.len(), 2);
+    for x in ker {
+        for r in a.iter() {
+            let s = r
+                .iter()
+                .zip(x.iter())
+                .fold(Ratio::new(0, 1), |acc, (a, b)| acc + a * b);
+            assert_eq!(s, Ratio::new(0, 1));
+        }
+    }
+}
+
+/// O(h w min(h, w)).
+/// Solve linear equation `a x = b`.
+///
+/// Returns `None` if there is no solution.
+/// Otherwise, returns `(x0, basis)` where the solution set is `x0 + span(basis)`.
+pub fn solve<T: Field>(a: &[Vec<T>], b: &[T]) -> Option<(Vec<T>, Matrix<T>)> {
+    assert_eq!(a.len(), b.len());
+    let w = width(a);
+
+    let mut c = a
+        .iter()
+        .zip(b.iter())
+        .map(|(r, x)| {
+            let mut r = r.clone();
+            r.resize(w, T::zero());
+            r.push(x.clone());
+            r
+        })
+        .collect::<Vec<_>>();
+
+    let pivots = rref(&mut c);
+    if pivots.last() == Some(&w) {
+        return None;
+    }
+
+    let mut x = vec![T::zero(); w];
+    for (r, &p) in pivots.iter().enumerate() {
+        x[p] = c[r][w].clone();
+    }
+
+    Some((x, kernel_of_rref(&c, &pivots, w)))
+}
+
+#[test]
+fn solve_test() {
+    type GF = crate::gf::GF<998244353>;
+    let a = vec![
+        vec![GF::new(1), GF::new(1), GF::new(1)],
+        vec![GF::new(1), GF::new(2), GF::new(3)],
+    ];
+    let b = vec![GF::new(6), GF::new(14)];
+    let (x, basis) = solve(&a, &b).unwrap();
+    assert_eq!(basis.len(), 1);
+    for t in 0..3 {
+        let y = x
+            .iter()
+            .zip(basis[0].iter())
+            .map(|(&x, &d)| x + d * t)
+            .collect::<Vec<_>>();
+        for (r, &b) in a.iter().zip(b.iter()) {
+            let s = r
+                .iter()
+                .zip(y.iter())
+                .fold(GF::new(0), |acc, (&a, &y)| acc + a * y);
+            assert_eq!(s, b);
+        }
+    }
+
+    let a = vec![vec![1.0, 1.0], vec![2.0, 2.0]];
+    assert!(solve(&a, &[1.0, 3.0]).is_none());
+
+    let a = vec![vec![2.0, 1.0], vec![1.0, 3.0]];
+    let (x, basis) = solve(&a, &[3.0, 5.0]).unwrap();
+    assert!(basis.is_empty());
+    assert!((x[0] - 0.8).abs() < 1e-9 && (x[1] - 1.4).abs() < 1e-9);
+}
+
+/// O(n^3 log m).
+/// Determinant of square matrix `a` modulo arbitrary `m`.
+///
+/// `m` need not be prime. Rows are reduced by the Euclidean algorithm instead of division.
+pub fn determinant_mod(a: &[Vec<u64>], m: u64) -> u64 {
+    let n = a.len();
+    assert!(a.iter().all(|r| r.len() == n));
+
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % m as u128) as u64;
+    let mut a = a
+        .iter()
+        .map(|r| r.iter().map(|&x| x % m).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut ret = 1 % m;
+
+    for c in 0..n {
+        for i in c + 1..n {
+            while a[i][c] != 0 {
+                let q = a[c][c] / a[i][c];
+                let (upper, lower) = a.split_at_mut(i);
+                let (rc, ri) = (&mut upper[c], &mut lower[0]);
+                for (x, &y) in rc[c..].iter_mut().zip(ri[c..].iter()) {
+                    let t = mul(q, y);
+                    *x = if *x >= t { *x - t } else { *x + (m - t) };
+                }
+                a.swap(c, i);
+                ret = (m - ret) % m;
+            }
+        }
+        ret = mul(ret, a[c][c]);
+    }
+
+    ret
+}
+
+#[test]
+fn determinant_mod_test() {
+    // modulus greater than 2^63
+    let m = u64::MAX - 58;
+    assert_eq!(determinant_mod(&[vec![100, 1], vec![1, 1]], m), 99);
+    assert_eq!(determinant_mod(&[vec![1, 100], vec![1, 1]], m), m - 99);
+    assert_eq!(determinant_mod(&[vec![m - 1, 2], vec![3, m - 5]], m), m - 1);
+
+    let a = vec![vec![2, 1, 1], vec![1, 3, 2], vec![1, 0, 0]];
+    // det = -1
+    assert_eq!(determinant_mod(&a, 12), 11);
+    assert_eq!(determinant_mod(&a, 1), 0);
+
+    let a = vec![vec![4, 6], vec![2, 9]];
+    // det = 24
+    assert_eq!(determinant_mod(&a, 10), 4);
+    assert_eq!(determinant_mod(&a, 8), 0);
+    assert_eq!(determinant_mod(&a, 1 << 40), 24);
+}
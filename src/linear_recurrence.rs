@@ -0,0 +1,169 @@
+use crate::gf::GF;
+use crate::poly::convolution;
+
+/// O(n^2).
+/// Find the shortest linear recurrence satisfied by `a` (Berlekamp-Massey algorithm).
+///
+/// Returns `c` such that `a[i] = c[0] * a[i - 1] + c[1] * a[i - 2] + ... + c[d - 1] * a[i - d]` for all `i >= d`.
+/// To determine a recurrence of order `d`, `a` should have at least `2d` terms.
+pub fn berlekamp_massey<const P: u64>(a: &[GF<P>]) -> Vec<GF<P>> {
+    let n = a.len();
+    let zero = GF::<P>::new(0);
+
+    // connection polynomials: `c[0] + c[1] x + ... + c[l] x^l` with `c[0] = 1`
+    let mut c = vec![zero; n + 1];
+    let mut b = vec![zero; n + 1];
+    c[0] = GF::new(1);
+    b[0] = GF::new(1);
+
+    let mut l = 0;
+    let mut m = 0;
+    let mut last_delta = GF::<P>::new(1);
+
+    for i in 0..n {
+        m += 1;
+        let delta = (1..=l).fold(a[i], |acc, j| acc + c[j] * a[i - j]);
+        if delta == zero {
+            continue;
+        }
+
+        let t = c.clone();
+        let f = delta / last_delta;
+        for j in m..=n {
+            c[j] -= f * b[j - m];
+        }
+        if 2 * l > i {
+            continue;
+        }
+
+        l = i + 1 - l;
+        b = t;
+        last_delta = delta;
+        m = 0;
+    }
+
+    c[1..=l].iter().map(|&x| -x).collect()
+}
+
+#[test]
+fn berlekamp_massey_test() {
+    type GF = crate::gf::GF<1000000007>;
+
+    let fib = [1, 1, 2, 3, 5, 8, 13, 21, 34, 55]
+        .iter()
+        .map(|&x| GF::new(x))
+        .collect::<Vec<_>>();
+    assert_eq!(berlekamp_massey(&fib), vec![GF::new(1), GF::new(1)]);
+
+    let a = [1, 2, 4, 8, 16, 32]
+        .iter()
+        .map(|&x| GF::new(x))
+        .collect::<Vec<_>>();
+    assert_eq!(berlekamp_massey(&a), vec![GF::new(2)]);
+
+    let a = [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1]
+        .iter()
+        .map(|&x| GF::new(x))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        berlekamp_massey(&a),
+        vec![GF::new(0), GF::new(0), GF::new(0), GF::new(1)]
+    );
+
+    assert!(berlekamp_massey(&[GF::new(0); 5]).is_empty());
+}
+
+/// O(d log d log n).
+/// Returns `[x^n] p(x) / q(x)` (Bostan-Mori algorithm). `q[0]` must not be zero.
+pub fn nth_coefficient<const P: u64>(p: &[GF<P>], q: &[GF<P>], n: u64) -> GF<P> {
+    let mut p = p.to_vec();
+    let mut q = q.to_vec();
+    let mut n = n;
+
+    while n > 0 {
+        let q_neg = q
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| if i % 2 == 0 { c } else { -c })
+            .collect::<Vec<_>>();
+        let u = convolution(&p, &q_neg);
+        let v = convolution(&q, &q_neg);
+        p = u.into_iter().skip((n % 2) as usize).step_by(2).collect();
+        q = v.into_iter().step_by(2).collect();
+        n /= 2;
+    }
+
+    match p.first() {
+        Some(&p0) => p0 / q[0],
+        None => GF::new(0),
+    }
+}
+
+/// O(d log d log n).
+/// Returns the `n`-th term (0-indexed) of the linear recurrent sequence.
+///
+/// `recurrence` is in the same form as the result of `berlekamp_massey`,
+/// and `initial` must contain at least the first `recurrence.len()` terms.
+///
+/// ```
+/// use competitive::linear_recurrence::nth_term;
+/// type GF = competitive::gf::GF<1000000007>;
+///
+/// // Fibonacci numbers
+/// let c = [GF::new(1), GF::new(1)];
+/// let a = [GF::new(0), GF::new(1)];
+/// assert_eq!(nth_term(&c, &a, 90), GF::new(2880067194370816120_u64 % 1000000007));
+/// ```
+pub fn nth_term<const P: u64>(recurrence: &[GF<P>], initial: &[GF<P>], n: u64) -> GF<P> {
+    let d = recurrence.len();
+    assert!(initial.len() >= d);
+
+    if n < initial.len() as u64 {
+        return initial[n as usize];
+    }
+
+    let mut q = vec![GF::<P>::new(1)];
+    q.extend(recurrence.iter().map(|&c| -c));
+    let mut p = convolution(&initial[..d], &q);
+    p.truncate(d);
+
+    nth_coefficient(&p, &q, n)
+}
+
+#[test]
+fn nth_term_test() {
+    type GF = crate::gf::GF<998244353>;
+
+    let c = [GF::new(2), GF::new(3), GF::new(5)];
+    let mut a = vec![GF::new(1), GF::new(4), GF::new(9)];
+    for i in 3..300 {
+        let x = c[0] * a[i - 1] + c[1] * a[i - 2] + c[2] * a[i - 3];
+        a.push(x);
+    }
+    for n in 0..300 {
+        assert_eq!(nth_term(&c, &a[..3], n as u64), a[n]);
+    }
+
+    assert_eq!(nth_term(&[], &[], 12345), GF::new(0));
+}
+
+/// O(k^2 + k log k log n).
+/// Guess the `n`-th term (0-indexed) of the sequence from its first terms `a`.
+///
+/// The shortest linear recurrence of `a` is found by `berlekamp_massey`, then extended by `nth_term`.
+pub fn guess_nth_term<const P: u64>(a: &[GF<P>], n: u64) -> GF<P> {
+    nth_term(&berlekamp_massey(a), a, n)
+}
+
+#[test]
+fn guess_nth_term_test() {
+    type GF = crate::gf::GF<1000000007>;
+
+    // sum of i^2 for i < n
+    let a = (0..10_u64)
+        .map(|n| GF::new((0..n).map(|i| i * i).sum::<u64>()))
+        .collect::<Vec<_>>();
+    let n = 1_000_000_000_000_000_000_u64;
+    let m = GF::new(n % 1000000007);
+    assert_eq!(guess_nth_term(&a, n), (m - 1) * m * (m * 2 - 1) / 6);
+}
@@ -0,0 +1,310 @@
+use crate::integer::{iroot, isqrt, quotient_blocks};
+use crate::prime::LinearSieve;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// O(n).
+/// Table of the multiplicative function `f` for `i < n` (`ret[0]` is zero).
+///
+/// `fpk(p, k)` should return `f(p^k)` for prime `p` and `k >= 1`.
+pub fn multiplicative_table<T>(n: usize, fpk: impl Fn(usize, usize) -> T) -> Vec<T>
+where
+    T: Clone + Mul<Output = T> + From<i64>,
+{
+    let ls = LinearSieve::new(n);
+    let mut ret = vec![T::from(0); n];
+    if n > 1 {
+        ret[1] = T::from(1);
+    }
+
+    // pw[i]: the largest power of lpf(i) dividing i, ex[i]: its exponent
+    let mut pw = vec![0; n];
+    let mut ex = vec![0; n];
+    for i in 2..n {
+        let p = ls.smallest_prime_factor(i);
+        let j = i / p;
+        if j > 1 && ls.smallest_prime_factor(j) == p {
+            pw[i] = pw[j] * p;
+            ex[i] = ex[j] + 1;
+        } else {
+            pw[i] = p;
+            ex[i] = 1;
+        }
+
+        ret[i] = if pw[i] == i {
+            fpk(p, ex[i])
+        } else {
+            ret[i / pw[i]].clone() * ret[pw[i]].clone()
+        };
+    }
+    ret
+}
+
+/// O(n).
+/// Table of Euler's totient function `phi(i)` for `i < n`
+pub fn phi_table<T: Clone + Mul<Output = T> + From<i64>>(n: usize) -> Vec<T> {
+    multiplicative_table(n, |p, k| T::from(((p - 1) * p.pow(k as u32 - 1)) as i64))
+}
+
+/// O(n).
+/// Table of Mobius function `mu(i)` for `i < n`
+pub fn mobius_table<T: Clone + Mul<Output = T> + From<i64>>(n: usize) -> Vec<T> {
+    multiplicative_table(n, |_, k| T::from(if k == 1 { -1 } else { 0 }))
+}
+
+/// O(n log k).
+/// Table of the divisor function `sigma_k(i) = sum_{d | i} d^k` for `i < n`
+pub fn sigma_table<T>(n: usize, k: u32) -> Vec<T>
+where
+    T: Clone + Add<Output = T> + Mul<Output = T> + From<i64>,
+{
+    multiplicative_table(n, |p, e| {
+        let mut pk = T::from(1);
+        for _ in 0..k {
+            pk = pk * T::from(p as i64);
+        }
+        let mut ret = T::from(1);
+        let mut cur = T::from(1);
+        for _ in 0..e {
+            cur = cur * pk.clone();
+            ret = ret + cur.clone();
+        }
+        ret
+    })
+}
+
+/// O(n).
+/// Table of the number of divisors `d(i)` for `i < n`
+pub fn divisor_count_table<T: Clone + Mul<Output = T> + From<i64>>(n: usize) -> Vec<T> {
+    multiplicative_table(n, |_, k| T::from(k as i64 + 1))
+}
+
+#[test]
+fn multiplicative_table_test() {
+    type GF = crate::gf::GF<1000000007>;
+    let n = 500;
+
+    let phi = phi_table::<i64>(n);
+    let mu = mobius_table::<i64>(n);
+    let sigma0 = sigma_table::<i64>(n, 0);
+    let sigma1 = sigma_table::<i64>(n, 1);
+    let sigma2 = sigma_table::<GF>(n, 2);
+    let d = divisor_count_table::<i64>(n);
+
+    let ls = LinearSieve::new(n);
+    let phi2 = ls.euler_phi_table();
+    let mu2 = ls.mobius_table();
+
+    for i in 1..n {
+        let ds = (1..=i).filter(|&j| i % j == 0).collect::<Vec<_>>();
+        assert_eq!(phi[i], phi2[i] as i64);
+        assert_eq!(mu[i], mu2[i]);
+        assert_eq!(sigma0[i], ds.len() as i64);
+        assert_eq!(d[i], ds.len() as i64);
+        assert_eq!(sigma1[i], ds.iter().sum::<usize>() as i64);
+        assert_eq!(sigma2[i], GF::new(ds.iter().map(|&j| j * j).sum::<usize>()));
+    }
+    assert_eq!(phi[0], 0);
+}
+
+/// O(n log n).
+/// Dirichlet convolution `h(i) = sum_{d | i} f(d) g(i / d)` for `1 <= i < n`.
+///
+/// Index `0` of arguments is ignored, and `ret[0]` is zero.
+pub fn dirichlet_convolution<T>(f: &[T], g: &[T]) -> Vec<T>
+where
+    T: Clone + Add<Output = T> + Mul<Output = T> + From<i64>,
+{
+    let n = f.len().min(g.len());
+    let mut ret = vec![T::from(0); n];
+    for i in 1..n {
+        for j in 1..=(n - 1) / i {
+            ret[i * j] = ret[i * j].clone() + f[i].clone() * g[j].clone();
+        }
+    }
+    ret
+}
+
+/// O(n log n).
+/// Dirichlet inverse `g` of `f`, such that `f * g = e` where `e(1) = 1` and `e(i) = 0` for `i > 1`.
+///
+/// `f[1]` must be invertible. Index `0` of the argument is ignored, and `ret[0]` is zero.
+pub fn dirichlet_inverse<T>(f: &[T]) -> Vec<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + From<i64>,
+{
+    let n = f.len();
+    let mut ret = vec![T::from(0); n];
+    if n <= 1 {
+        return ret;
+    }
+
+    // acc[i] = sum_{d | i, d < i} g(d) f(i / d)
+    let mut acc = vec![T::from(0); n];
+    for i in 1..n {
+        let e = T::from(if i == 1 { 1 } else { 0 });
+        ret[i] = (e - acc[i].clone()) / f[1].clone();
+        for j in 2..=(n - 1) / i {
+            acc[i * j] = acc[i * j].clone() + ret[i].clone() * f[j].clone();
+        }
+    }
+    ret
+}
+
+#[test]
+fn dirichlet_test() {
+    type GF = crate::gf::GF<998244353>;
+    let n = 300;
+
+    let one = vec![1_i64; n];
+    let mu = mobius_table::<i64>(n);
+    let e = dirichlet_convolution(&mu, &one);
+    assert_eq!(e[1], 1);
+    assert!(e[2..].iter().all(|&x| x == 0));
+    assert_eq!(dirichlet_inverse(&one)[1..], mu[1..]);
+
+    // phi * 1 = id
+    let phi = phi_table::<i64>(n);
+    let id = dirichlet_convolution(&phi, &one);
+    for (i, &x) in id.iter().enumerate().skip(1) {
+        assert_eq!(x, i as i64);
+    }
+
+    let f = (0..n).map(|i| GF::new(i * i + 3)).collect::<Vec<_>>();
+    let g = dirichlet_inverse(&f);
+    let e = dirichlet_convolution(&f, &g);
+    assert_eq!(e[1], GF::new(1));
+    assert!(e[2..].iter().all(|&x| x == GF::new(0)));
+}
+
+struct DuSieve<'a, T, G, H> {
+    n: u64,
+    small: &'a [T],
+    large: Vec<Option<T>>,
+    sum_g: G,
+    sum_h: H,
+}
+
+impl<T, G, H> DuSieve<'_, T, G, H>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    G: Fn(u64) -> T,
+    H: Fn(u64) -> T,
+{
+    fn sum(&mut self, x: u64) -> T {
+        if (x as usize) < self.small.len() {
+            return self.small[x as usize].clone();
+        }
+
+        let k = (self.n / x) as usize;
+        if let Some(ret) = &self.large[k] {
+            return ret.clone();
+        }
+
+        // sum_{d=1}^{x} g(d) S(x / d) = H(x)
+        let mut ret = (self.sum_h)(x);
+        for (q, r) in quotient_blocks(x) {
+            if *r.end() < 2 {
+                continue;
+            }
+            let l = (*r.start()).max(2);
+            let gs = (self.sum_g)(*r.end()) - (self.sum_g)(l - 1);
+            ret = ret - gs * self.sum(q);
+        }
+
+        self.large[k] = Some(ret.clone());
+        ret
+    }
+}
+
+/// O(n^(2/3)) with an appropriate `small` table.
+/// Prefix sum `S(n) = sum_{i=1}^{n} f(i)` of an arithmetic function by Du's sieve.
+///
+/// * `small` - prefix sums `S(x)` for `x < small.len()`. `small.len()` must be greater than `sqrt(n)`,
+///   and around `n^(2/3)` is optimal.
+/// * `sum_g` - prefix sum of `g` such that `g(1) = 1`
+/// * `sum_h` - prefix sum of `h = f * g` (Dirichlet convolution)
+///
+/// Both `sum_g` and `sum_h` should be computable in O(1).
+pub fn du_sieve<T>(n: u64, small: &[T], sum_g: impl Fn(u64) -> T, sum_h: impl Fn(u64) -> T) -> T
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    assert!(small.len() as u64 > isqrt(n));
+    let large_len = (n / small.len() as u64) as usize + 2;
+    let mut du = DuSieve {
+        n,
+        small,
+        large: vec![None; large_len],
+        sum_g,
+        sum_h,
+    };
+    du.sum(n)
+}
+
+fn du_sieve_threshold(n: u64) -> usize {
+    let t = iroot(n, 3);
+    (t * t).max(isqrt(n) + 1) as usize + 1
+}
+
+fn prefix_sum<T: Clone + Add<Output = T>>(v: Vec<T>) -> Vec<T> {
+    let mut ret = v;
+    for i in 1..ret.len() {
+        ret[i] = ret[i - 1].clone() + ret[i].clone();
+    }
+    ret
+}
+
+/// O(n^(2/3)).
+/// Returns `sum_{i=1}^{n} phi(i)`.
+///
+/// The result exceeds `i64` for `n` around `5e9`. Use `i128` or `GF<P>` for larger `n`.
+pub fn phi_prefix_sum<T>(n: u64) -> T
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + From<i64>,
+{
+    let small = prefix_sum(phi_table::<T>(du_sieve_threshold(n)));
+    du_sieve(
+        n,
+        &small,
+        |x| T::from(x as i64),
+        |x| {
+            // x (x + 1) / 2 without overflow
+            if x % 2 == 0 {
+                T::from((x / 2) as i64) * T::from(x as i64 + 1)
+            } else {
+                T::from(x as i64) * T::from(x.div_ceil(2) as i64)
+            }
+        },
+    )
+}
+
+/// O(n^(2/3)).
+/// Returns `sum_{i=1}^{n} mu(i)` (Mertens function)
+pub fn mobius_prefix_sum<T>(n: u64) -> T
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + From<i64>,
+{
+    let small = prefix_sum(mobius_table::<T>(du_sieve_threshold(n)));
+    du_sieve(n, &small, |x| T::from(x as i64), |_| T::from(1))
+}
+
+#[test]
+fn prefix_sum_test() {
+    type GF = crate::gf::GF<1000000007>;
+
+    let n = 100000;
+    let phi = prefix_sum(phi_table::<i64>(n + 1));
+    let mu = prefix_sum(mobius_table::<i64>(n + 1));
+    for &x in &[0, 1, 2, 10, 1000, 99999, 100000] {
+        assert_eq!(phi_prefix_sum::<i64>(x as u64), phi[x]);
+        assert_eq!(mobius_prefix_sum::<i64>(x as u64), mu[x]);
+        assert_eq!(phi_prefix_sum::<GF>(x as u64), GF::new(phi[x]));
+    }
+
+    // small table of the minimum size
+    let small = mu[..317].to_vec();
+    assert_eq!(du_sieve(n as u64, &small, |x| x as i64, |_| 1), mu[n]);
+
+    assert_eq!(mobius_prefix_sum::<i64>(1_000_000_000), -222);
+    assert_eq!(phi_prefix_sum::<i64>(1_000_000_000), 303963551173008414);
+}
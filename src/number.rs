@@ -1,4 +1,4 @@
-use std::ops::{Div, Mul, MulAssign, Rem};
+use std::ops::{Add, Div, Mul, MulAssign, Rem, Sub};
 
 /// Calculate factorial
 pub fn fact<T: MulAssign<T> + From<i32>>(n: usize) -> T {
@@ -87,3 +87,619 @@ where
         }
     }
 }
+
+/// O(1).
+/// Calculate `a * b % m` without overflow
+pub(crate) const fn mul_mod_u64(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// O(log e).
+/// Calculate `a.pow(e) % m` without overflow
+pub(crate) const fn pow_mod_u64(a: u64, e: u64, m: u64) -> u64 {
+    let mut ret = 1 % m;
+    let mut a = a % m;
+    let mut e = e;
+    while e > 0 {
+        if e & 1 != 0 {
+            ret = mul_mod_u64(ret, a, m);
+        }
+        a = mul_mod_u64(a, a, m);
+        e >>= 1;
+    }
+    ret
+}
+
+/// O(log min(a, b)).
+/// Extended Euclidean algorithm.
+/// Returns `(g, x, y)` such that `a * x + b * y = g = gcd(a, b)` and `g >= 0`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+#[test]
+fn ext_gcd_test() {
+    for a in -20..=20 {
+        for b in -20..=20 {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, num::integer::gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+}
+
+/// O(log m).
+/// Inverse of `a` modulo `m` (`m` need not be prime).
+/// Returns `None` if `a` and `m` are not coprime.
+pub fn inv_mod(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0);
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    if g == 1 {
+        Some(x.rem_euclid(m))
+    } else {
+        None
+    }
+}
+
+#[test]
+fn inv_mod_test() {
+    assert_eq!(inv_mod(3, 10), Some(7));
+    assert_eq!(inv_mod(-3, 10), Some(3));
+    assert_eq!(inv_mod(4, 10), None);
+    assert_eq!(inv_mod(5, 1), Some(0));
+    for a in 1..1000 {
+        assert_eq!(inv_mod(a, 1000000007).unwrap() * a % 1000000007, 1);
+    }
+}
+
+/// O(n log m).
+/// Chinese remainder theorem.
+///
+/// Solves `x = r_i (mod m_i)` for all `(r_i, m_i)` in `rm`. Moduli need not be coprime.
+/// Returns `Some((x, lcm))` where the solutions are `x + k * lcm` with `0 <= x < lcm`,
+/// or `None` if there is no solution.
+///
+/// The lcm of all moduli must fit in `i64`.
+///
+/// ```
+/// # use competitive::number::crt;
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+/// ```
+pub fn crt(rm: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut r0: i128 = 0;
+    let mut m0: i128 = 1;
+    for &(r1, m1) in rm {
+        assert!(m1 > 0);
+        let (r1, m1) = ((r1 as i128).rem_euclid(m1 as i128), m1 as i128);
+
+        let (g, p, _) = ext_gcd(m0 as i64, m1 as i64);
+        let (g, p) = (g as i128, p as i128);
+        if (r1 - r0) % g != 0 {
+            return None;
+        }
+
+        // x = r0 + m0 * t, where m0 * t = r1 - r0 (mod m1)
+        let u = m1 / g;
+        let t = ((r1 - r0) / g % u * p).rem_euclid(u);
+        r0 += m0 * t;
+        m0 *= u;
+        assert!(m0 <= i64::MAX as i128);
+    }
+    Some((r0 as i64, m0 as i64))
+}
+
+#[test]
+fn crt_test() {
+    assert_eq!(crt(&[]), Some((0, 1)));
+    assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+    assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+    assert_eq!(crt(&[(1, 2), (2, 4)]), None);
+
+    let m1 = 1_000_000_007;
+    let m2 = 998_244_353;
+    let x = 123_456_789_012_345_678_i64;
+    assert_eq!(crt(&[(x % m1, m1), (x % m2, m2)]), Some((x, m1 * m2)));
+
+    for a in 1..12 {
+        for b in 1..12 {
+            for x in 0..12 {
+                for y in 0..12 {
+                    let expected = (0..a * b).find(|t| t % a == x % a && t % b == y % b);
+                    let lcm = num::integer::lcm(a, b);
+                    assert_eq!(crt(&[(x, a), (y, b)]), expected.map(|t| (t, lcm)));
+                }
+            }
+        }
+    }
+}
+
+/// O(n^2 log m).
+/// Garner's algorithm.
+///
+/// Returns `x mod m` where `x` is the unique value in `[0, m_1 m_2 ... m_n)` such that `x = r_i (mod m_i)`.
+/// Moduli `m_i` must be pairwise coprime, but `m` is arbitrary.
+/// This is useful when the product of moduli does not fit in an integer.
+pub fn garner(rm: &[(i64, i64)], m: i64) -> i64 {
+    assert!(m > 0);
+    let n = rm.len();
+
+    // x = c_0 + c_1 m_0 + c_2 m_0 m_1 + ...
+    // prod[i] = m_0 m_1 ... m_{k-1} mod m_i (m_n = m), sum[i] = c_0 + c_1 m_0 + ... mod m_i
+    let mods = rm.iter().map(|r| r.1).chain(Some(m)).collect::<Vec<_>>();
+    let mut prod = vec![1_i128; n + 1];
+    let mut sum = vec![0_i128; n + 1];
+
+    for (k, &(r, mk)) in rm.iter().enumerate() {
+        assert!(mk > 0);
+        let mk128 = mk as i128;
+        let inv = inv_mod((prod[k] % mk128) as i64, mk).expect("moduli must be coprime") as i128;
+        let c = ((r as i128 - sum[k]).rem_euclid(mk128) * inv) % mk128;
+        for i in k + 1..=n {
+            let mi = mods[i] as i128;
+            sum[i] = (sum[i] + c * prod[i]) % mi;
+            prod[i] = prod[i] * mk128 % mi;
+        }
+    }
+
+    (sum[n] % m as i128) as i64
+}
+
+#[test]
+fn garner_test() {
+    let rm = [(2, 3), (3, 5), (2, 7)];
+    assert_eq!(garner(&rm, 1_000_000_007), 23);
+    assert_eq!(garner(&rm, 10), 3);
+
+    let ms = [1_000_000_007, 998_244_353, 1_000_000_009];
+    let x: i128 = 123_456_789_012_345_678_901_234_567;
+    let rm = ms
+        .iter()
+        .map(|&m| ((x % m as i128) as i64, m))
+        .collect::<Vec<_>>();
+    assert_eq!(garner(&rm, 1_000_000_000), (x % 1_000_000_000) as i64);
+    assert_eq!(garner(&rm, 1), 0);
+}
+
+/// O(n^2).
+/// Generate table of unsigned Stirling numbers of the first kind.
+/// `ret[i][j]` is the number of permutations of `i` elements with `j` cycles.
+pub fn gen_stirling1_table<T: Add<Output = T> + Mul<Output = T> + From<i32> + Clone>(
+    n: usize,
+) -> Vec<Vec<T>> {
+    let mut ret = vec![vec![T::from(0); n]; n];
+    if n > 0 {
+        ret[0][0] = T::from(1);
+    }
+    for i in 1..n {
+        for j in 1..=i {
+            ret[i][j] = T::from((i - 1) as i32) * ret[i - 1][j].clone() + ret[i - 1][j - 1].clone();
+        }
+    }
+    ret
+}
+
+/// O(n k).
+/// Unsigned Stirling number of the first kind `[n, k]`
+pub fn stirling1<T: Add<Output = T> + Mul<Output = T> + From<i32> + Clone>(
+    n: usize,
+    k: usize,
+) -> T {
+    if k > n {
+        return T::from(0);
+    }
+
+    // row[j] = [i, j] for j <= k
+    let mut row = vec![T::from(0); k + 1];
+    row[0] = T::from(1);
+    for i in 1..=n {
+        for j in (1..=k.min(i)).rev() {
+            row[j] = T::from((i - 1) as i32) * row[j].clone() + row[j - 1].clone();
+        }
+        row[0] = T::from(0);
+    }
+    row[k].clone()
+}
+
+#[test]
+fn stirling1_test() {
+    let t = gen_stirling1_table::<i64>(7);
+    assert_eq!(t[4], vec![0, 6, 11, 6, 1, 0, 0]);
+    assert_eq!(t[6][2], 274);
+    for (n, row) in t.iter().enumerate() {
+        for (k, &x) in row.iter().enumerate() {
+            assert_eq!(stirling1::<i64>(n, k), x);
+        }
+    }
+}
+
+/// O(n^2).
+/// Generate table of Stirling numbers of the second kind.
+/// `ret[i][j]` is the number of ways to partition `i` elements into `j` non-empty subsets.
+pub fn gen_stirling2_table<T: Add<Output = T> + Mul<Output = T> + From<i32> + Clone>(
+    n: usize,
+) -> Vec<Vec<T>> {
+    let mut ret = vec![vec![T::from(0); n]; n];
+    if n > 0 {
+        ret[0][0] = T::from(1);
+    }
+    for i in 1..n {
+        for j in 1..=i {
+            ret[i][j] = T::from(j as i32) * ret[i - 1][j].clone() + ret[i - 1][j - 1].clone();
+        }
+    }
+    ret
+}
+
+/// O(k log n).
+/// Stirling number of the second kind `{n, k}`, calculated by inclusion-exclusion.
+///
+/// `T` must be signed, since intermediate values can be negative.
+pub fn stirling2<T>(n: usize, k: usize) -> T
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + From<i32> + Clone,
+{
+    // {n, k} = 1/k! sum_{i=0}^{k} (-1)^i C(k, i) (k - i)^n
+    let mut ret = T::from(0);
+    let mut c = T::from(1);
+    for i in 0..=k {
+        let t = c.clone() * pow_usize(T::from((k - i) as i32), n);
+        ret = if i % 2 == 0 { ret + t } else { ret - t };
+        c = c * T::from((k - i) as i32) / T::from(i as i32 + 1);
+    }
+    ret / fact_by_mul::<T>(k)
+}
+
+fn pow_usize<T: Mul<Output = T> + From<i32> + Clone>(a: T, n: usize) -> T {
+    let mut ret = T::from(1);
+    let mut a = a;
+    let mut n = n;
+    while n > 0 {
+        if n & 1 != 0 {
+            ret = ret * a.clone();
+        }
+        a = a.clone() * a;
+        n >>= 1;
+    }
+    ret
+}
+
+fn fact_by_mul<T: Mul<Output = T> + From<i32>>(n: usize) -> T {
+    (2..=n).fold(T::from(1), |acc, i| acc * T::from(i as i32))
+}
+
+#[test]
+fn stirling2_test() {
+    type GF = crate::gf::GF<1000000007>;
+    let t = gen_stirling2_table::<i64>(8);
+    assert_eq!(t[4], vec![0, 1, 7, 6, 1, 0, 0, 0]);
+    assert_eq!(t[7][3], 301);
+    for (n, row) in t.iter().enumerate() {
+        for (k, &x) in row.iter().enumerate() {
+            assert_eq!(stirling2::<i64>(n, k), x);
+            assert_eq!(stirling2::<GF>(n, k), GF::new(x));
+        }
+    }
+}
+
+/// O(n^2).
+/// Generate table of Bell numbers, the numbers of partitions of a set of size `i` for `i < n`.
+pub fn gen_bell_table<T: Add<Output = T> + From<i32> + Clone>(n: usize) -> Vec<T> {
+    // Bell triangle
+    let mut ret = Vec::with_capacity(n);
+    let mut row = vec![T::from(1)];
+    for _ in 0..n {
+        ret.push(row[0].clone());
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(row[row.len() - 1].clone());
+        for x in row.iter() {
+            let y = next[next.len() - 1].clone() + x.clone();
+            next.push(y);
+        }
+        row = next;
+    }
+    ret
+}
+
+/// O(n^2).
+/// Bell number, the number of partitions of a set of size `n`
+pub fn bell<T: Add<Output = T> + From<i32> + Clone>(n: usize) -> T {
+    gen_bell_table::<T>(n + 1)[n].clone()
+}
+
+#[test]
+fn bell_test() {
+    assert_eq!(
+        gen_bell_table::<i64>(10),
+        vec![1, 1, 2, 5, 15, 52, 203, 877, 4140, 21147]
+    );
+    assert_eq!(bell::<i64>(0), 1);
+    assert_eq!(bell::<i128>(25), 4638590332229999353);
+}
+
+/// O(n sqrt n).
+/// Generate table of partition numbers `p(i)` for `i < n` by Euler's pentagonal number theorem.
+pub fn gen_partition_table<T: Add<Output = T> + Sub<Output = T> + From<i32> + Clone>(
+    n: usize,
+) -> Vec<T> {
+    let mut ret: Vec<T> = Vec::with_capacity(n);
+    for i in 0..n {
+        if i == 0 {
+            ret.push(T::from(1));
+            continue;
+        }
+        let mut x = T::from(0);
+        for k in 1.. {
+            let g1 = k * (3 * k - 1) / 2;
+            if g1 > i {
+                break;
+            }
+            let g2 = k * (3 * k + 1) / 2;
+            let mut t = ret[i - g1].clone();
+            if g2 <= i {
+                t = t + ret[i - g2].clone();
+            }
+            x = if k % 2 == 1 { x + t } else { x - t };
+        }
+        ret.push(x);
+    }
+    ret
+}
+
+/// O(n sqrt n).
+/// Partition number `p(n)`, the number of ways to write `n` as a sum of positive integers
+pub fn partition<T: Add<Output = T> + Sub<Output = T> + From<i32> + Clone>(n: usize) -> T {
+    gen_partition_table::<T>(n + 1)[n].clone()
+}
+
+#[test]
+fn partition_test() {
+    assert_eq!(
+        gen_partition_table::<i64>(12),
+        vec![1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42, 56]
+    );
+    assert_eq!(partition::<i64>(100), 190569292);
+    type GF = crate::gf::GF<1000000007>;
+    assert_eq!(partition::<GF>(100), GF::new(190569292));
+}
+
+/// O(n^2).
+/// Generate table of Eulerian numbers.
+/// `ret[i][j]` is the number of permutations of `i` elements with exactly `j` ascents.
+pub fn gen_eulerian_table<T: Add<Output = T> + Mul<Output = T> + From<i32> + Clone>(
+    n: usize,
+) -> Vec<Vec<T>> {
+    let mut ret = vec![vec![T::from(0); n]; n];
+    if n > 0 {
+        ret[0][0] = T::from(1);
+    }
+    for i in 1..n {
+        ret[i][0] = T::from(1);
+        for j in 1..i {
+            ret[i][j] = T::from((i - j) as i32) * ret[i - 1][j - 1].clone()
+                + T::from(j as i32 + 1) * ret[i - 1][j].clone();
+        }
+    }
+    ret
+}
+
+/// O(m log n).
+/// Eulerian number `A(n, m)`, calculated by `sum_{k=0}^{m} (-1)^k C(n + 1, k) (m + 1 - k)^n`.
+///
+/// `T` must be signed, since intermediate values can be negative.
+pub fn eulerian<T>(n: usize, m: usize) -> T
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + From<i32> + Clone,
+{
+    if n == 0 {
+        return T::from(if m == 0 { 1 } else { 0 });
+    }
+    if m >= n {
+        return T::from(0);
+    }
+
+    let mut ret = T::from(0);
+    let mut c = T::from(1);
+    for k in 0..=m {
+        let t = c.clone() * pow_usize(T::from((m + 1 - k) as i32), n);
+        ret = if k % 2 == 0 { ret + t } else { ret - t };
+        c = c * T::from((n + 1 - k) as i32) / T::from(k as i32 + 1);
+    }
+    ret
+}
+
+#[test]
+fn eulerian_test() {
+    type GF = crate::gf::GF<998244353>;
+    let t = gen_eulerian_table::<i64>(8);
+    assert_eq!(t[4], vec![1, 11, 11, 1, 0, 0, 0, 0]);
+    assert_eq!(t[7][3], 2416);
+    for (n, row) in t.iter().enumerate() {
+        for (m, &x) in row.iter().enumerate() {
+            assert_eq!(eulerian::<i64>(n, m), x);
+            assert_eq!(eulerian::<GF>(n, m), GF::new(x));
+        }
+    }
+}
+
+/// Binomial coefficients modulo a small prime `p` by Lucas's theorem.
+///
+/// Construction takes O(p), and each query takes O(log_p n).
+pub struct CombModPrime {
+    p: u64,
+    fact: Vec<u64>,
+    inv_fact: Vec<u64>,
+}
+
+impl CombModPrime {
+    /// O(p).
+    /// `p` must be prime.
+    pub fn new(p: u64) -> Self {
+        let n = p as usize;
+        let mut fact = vec![1 % p; n];
+        for i in 1..n {
+            fact[i] = mul_mod_u64(fact[i - 1], i as u64, p);
+        }
+        let mut inv_fact = vec![1 % p; n];
+        inv_fact[n - 1] = pow_mod_u64(fact[n - 1], p - 2, p);
+        for i in (1..n).rev() {
+            inv_fact[i - 1] = mul_mod_u64(inv_fact[i], i as u64, p);
+        }
+        Self { p, fact, inv_fact }
+    }
+
+    /// O(log_p n).
+    /// `C(n, k) mod p`
+    pub fn comb(&self, n: u64, k: u64) -> u64 {
+        let p = self.p;
+        let (mut n, mut k) = (n, k);
+        let mut ret = 1 % p;
+        while k > 0 {
+            let (ni, ki) = ((n % p) as usize, (k % p) as usize);
+            if ki > ni {
+                return 0;
+            }
+            ret = mul_mod_u64(ret, self.fact[ni], p);
+            ret = mul_mod_u64(ret, self.inv_fact[ki], p);
+            ret = mul_mod_u64(ret, self.inv_fact[ni - ki], p);
+            n /= p;
+            k /= p;
+        }
+        ret
+    }
+}
+
+#[test]
+fn comb_mod_prime_test() {
+    for &p in &[2, 3, 5, 7, 13] {
+        let c = CombModPrime::new(p);
+        for n in 0..40 {
+            for k in 0..=n + 1 {
+                let expected = if k > n {
+                    0
+                } else {
+                    (comb::<f64>(n as usize, k as usize).round() as u64) % p
+                };
+                assert_eq!(c.comb(n, k), expected);
+            }
+        }
+    }
+    // C(n, p) = floor(n / p) (mod p) by Lucas's theorem
+    let p = 1000003;
+    let c = CombModPrime::new(p);
+    let n = 1_000_000_000_000_000_000;
+    assert_eq!(c.comb(n, p), n / p % p);
+}
+
+/// Binomial coefficients modulo a prime power `p^e`.
+///
+/// `C(n, k) = p^v * n!_p / (k!_p * (n - k)!_p)`, where `n!_p` is `n!` with all factors of `p` removed
+/// and `v` is the exponent of `p` in `C(n, k)` by Legendre's formula.
+/// `n!_p mod p^e` is computed using the periodicity of the products of integers coprime to `p`.
+/// Construction takes O(p^e), and each query takes O(log_p n).
+pub struct CombModPrimePower {
+    p: u64,
+    e: u32,
+    pe: u64,
+    // table[i] = product of j in 1..=i coprime to p, mod p^e
+    table: Vec<u64>,
+}
+
+impl CombModPrimePower {
+    /// O(p^e).
+    /// `p` must be prime.
+    pub fn new(p: u64, e: u32) -> Self {
+        assert!(e > 0);
+        let pe = p.pow(e);
+        let mut table = vec![1 % pe; pe as usize + 1];
+        for i in 1..=pe as usize {
+            table[i] = if (i as u64).is_multiple_of(p) {
+                table[i - 1]
+            } else {
+                mul_mod_u64(table[i - 1], i as u64, pe)
+            };
+        }
+        Self { p, e, pe, table }
+    }
+
+    /// `n!` with all factors of `p` removed, modulo `p^e`
+    fn fact_without_p(&self, n: u64) -> u64 {
+        let mut n = n;
+        let mut ret = 1 % self.pe;
+        while n > 0 {
+            let full = pow_mod_u64(self.table[self.pe as usize], n / self.pe, self.pe);
+            ret = mul_mod_u64(ret, full, self.pe);
+            ret = mul_mod_u64(ret, self.table[(n % self.pe) as usize], self.pe);
+            n /= self.p;
+        }
+        ret
+    }
+
+    /// Exponent of `p` in `n!`
+    fn legendre(&self, n: u64) -> u64 {
+        let mut n = n;
+        let mut ret = 0;
+        while n > 0 {
+            n /= self.p;
+            ret += n;
+        }
+        ret
+    }
+
+    /// O(log_p n).
+    /// `C(n, k) mod p^e`
+    pub fn comb(&self, n: u64, k: u64) -> u64 {
+        if k > n {
+            return 0;
+        }
+
+        let v = self.legendre(n) - self.legendre(k) - self.legendre(n - k);
+        if v >= self.e as u64 {
+            return 0;
+        }
+
+        let den = mul_mod_u64(self.fact_without_p(k), self.fact_without_p(n - k), self.pe);
+        let inv = inv_mod(den as i64, self.pe as i64).unwrap() as u64;
+        let ret = mul_mod_u64(self.fact_without_p(n), inv, self.pe);
+        mul_mod_u64(ret, self.p.pow(v as u32), self.pe)
+    }
+}
+
+#[test]
+fn comb_mod_prime_power_test() {
+    let mut pascal = vec![vec![0_u128; 61]; 61];
+    for n in 0..61 {
+        pascal[n][0] = 1;
+        for k in 1..=n {
+            pascal[n][k] = pascal[n - 1][k - 1] + pascal[n - 1][k];
+        }
+    }
+
+    for &(p, e) in &[(2, 1), (2, 5), (3, 3), (5, 2), (7, 1)] {
+        let c = CombModPrimePower::new(p, e);
+        let pe = p.pow(e);
+        for n in 0..60 {
+            for k in 0..=n + 1 {
+                let expected = if k > n {
+                    0
+                } else {
+                    (pascal[n as usize][k as usize] % pe as u128) as u64
+                };
+                assert_eq!(c.comb(n, k), expected, "C({}, {}) mod {}^{}", n, k, p, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use crate::gf::GF;
+use crate::number::pow_mod_u64;
+
+/// Smallest primitive root of prime `p`
+const fn primitive_root(p: u64) -> u64 {
+    if p == 2 {
+        return 1;
+    }
+
+    let mut fs = [0_u64; 64];
+    let mut k = 0;
+    let mut x = p - 1;
+    let mut d = 2;
+    while d * d <= x {
+        if x.is_multiple_of(d) {
+            fs[k] = d;
+            k += 1;
+            while x.is_multiple_of(d) {
+                x /= d;
+            }
+        }
+        d += 1;
+    }
+    if x > 1 {
+        fs[k] = x;
+        k += 1;
+    }
+
+    let mut g = 2;
+    loop {
+        let mut i = 0;
+        while i < k && pow_mod_u64(g, (p - 1) / fs[i], p) != 1 {
+            i += 1;
+        }
+        if i == k {
+            return g;
+        }
+        g += 1;
+    }
+}
+
+struct NttInfo<const P: u64>;
+
+impl<const P: u64> NttInfo<P> {
+    const ROOT: u64 = primitive_root(P);
+    const RANK2: u32 = (P - 1).trailing_zeros();
+}
+
+/// In-place number theoretic transform. `a.len()` must be a power of 2 dividing `P - 1`.
+fn ntt<const P: u64>(a: &mut [GF<P>], invert: bool) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let mut w = GF::<P>::new(NttInfo::<P>::ROOT).pow((P - 1) / len as u64);
+        if invert {
+            w = w.recip();
+        }
+        for chunk in a.chunks_mut(len) {
+            let (l, r) = chunk.split_at_mut(len / 2);
+            let mut wn = GF::<P>::new(1);
+            for (x, y) in l.iter_mut().zip(r.iter_mut()) {
+                let u = *x;
+                let v = *y * wn;
+                *x = u + v;
+                *y = u - v;
+                wn *= w;
+            }
+        }
+        len <<= 1;
+    }
+
+    if invert {
+        let inv = GF::<P>::new(n as u64).recip();
+        for x in a.iter_mut() {
+            *x *= inv;
+        }
+    }
+}
+
+fn convolution_naive<const P: u64>(a: &[GF<P>], b: &[GF<P>]) -> Vec<GF<P>> {
+    let mut ret = vec![GF::<P>::new(0); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            ret[i + j] += x * y;
+        }
+    }
+    ret
+}
+
+fn convolution_ntt<const P: u64>(a: &[GF<P>], b: &[GF<P>]) -> Vec<GF<P>> {
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+
+    let mut fa = a.to_vec();
+    fa.resize(n, GF::new(0));
+    let mut fb = b.to_vec();
+    fb.resize(n, GF::new(0));
+
+    ntt(&mut fa, false);
+    ntt(&mut fb, false);
+    for (x, y) in fa.iter_mut().zip(fb.iter()) {
+        *x *= *y;
+    }
+    ntt(&mut fa, true);
+
+    fa.truncate(len);
+    fa
+}
+
+fn convolution_by<const Q: u64>(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let a = a.iter().map(|&x| GF::<Q>::new(x)).collect::<Vec<_>>();
+    let b = b.iter().map(|&x| GF::<Q>::new(x)).collect::<Vec<_>>();
+    convolution_ntt(&a, &b).into_iter().map(|x| x.0).collect()
+}
+
+/// O((n + m) log (n + m)).
+/// Convolution of `a` and `b`, i.e. the product of two polynomials.
+///
+/// Number theoretic transform is used directly if `P` is NTT-friendly (like 998244353).
+/// Otherwise, the results over three NTT-friendly primes are combined by Chinese remaindering.
+///
+/// ```
+/// use competitive::poly::convolution;
+/// type GF = competitive::gf::GF<1000000007>;
+///
+/// let a = vec![GF::new(1), GF::new(2)];
+/// let b = vec![GF::new(3), GF::new(4)];
+/// assert_eq!(convolution(&a, &b), vec![GF::new(3), GF::new(10), GF::new(8)]);
+/// ```
+pub fn convolution<const P: u64>(a: &[GF<P>], b: &[GF<P>]) -> Vec<GF<P>> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    if a.len().min(b.len()) <= 32 {
+        return convolution_naive(a, b);
+    }
+
+    let n = (a.len() + b.len() - 1).next_power_of_two();
+    if n.trailing_zeros() <= NttInfo::<P>::RANK2 {
+        return convolution_ntt(a, b);
+    }
+
+    const P1: u64 = 998244353;
+    const P2: u64 = 167772161;
+    const P3: u64 = 469762049;
+
+    let a = a.iter().map(|x| x.0).collect::<Vec<_>>();
+    let b = b.iter().map(|x| x.0).collect::<Vec<_>>();
+    let r1 = convolution_by::<P1>(&a, &b);
+    let r2 = convolution_by::<P2>(&a, &b);
+    let r3 = convolution_by::<P3>(&a, &b);
+
+    let inv_p1_p2 = GF::<P2>::new(P1).recip();
+    let inv_p1p2_p3 = (GF::<P3>::new(P1) * P2).recip();
+    let p1_p = GF::<P>::new(P1);
+    let p1p2_p = p1_p * P2;
+
+    r1.into_iter()
+        .zip(r2)
+        .zip(r3)
+        .map(|((x1, x2), x3)| {
+            let t1 = ((GF::<P2>::new(x2) - x1) * inv_p1_p2).0;
+            let t2 = ((GF::<P3>::new(x3) - x1 - GF::<P3>::new(P1) * t1) * inv_p1p2_p3).0;
+            GF::<P>::new(x1) + p1_p * t1 + p1p2_p * t2
+        })
+        .collect()
+}
+
+#[test]
+fn convolution_test() {
+    fn check<const P: u64>() {
+        let a = (0..200_u64)
+            .map(|i| GF::<P>::new(i * i * 12345 % P))
+            .collect::<Vec<_>>();
+        let b = (0..100_u64)
+            .map(|i| GF::<P>::new((i * 987654321 + 7) % P))
+            .collect::<Vec<_>>();
+        assert_eq!(convolution(&a, &b), convolution_naive(&a, &b));
+        assert_eq!(convolution(&a[..5], &b), convolution_naive(&a[..5], &b));
+    }
+
+    check::<998244353>();
+    check::<1000000007>();
+    check::<2>();
+
+    assert!(convolution::<7>(&[], &[GF::new(1)]).is_empty());
+}
+
+/// O(n log n).
+/// First `n` coefficients of `1 / a(x)`. `a[0]` must not be zero.
+pub fn poly_inv<const P: u64>(a: &[GF<P>], n: usize) -> Vec<GF<P>> {
+    assert!(!a.is_empty() && a[0].0 != 0);
+
+    let mut ret = vec![a[0].recip()];
+    let mut m = 1;
+    while m < n {
+        m *= 2;
+        // ret <- ret * (2 - a * ret) mod x^m
+        let mut t = convolution(&a[..m.min(a.len())], &ret);
+        t.truncate(m);
+        let mut t = convolution(&t, &ret);
+        t.truncate(m);
+        t.resize(m, GF::new(0));
+        for (i, x) in t.iter_mut().enumerate() {
+            *x = ret.get(i).map_or(GF::new(0), |&r| r * 2) - *x;
+        }
+        ret = t;
+    }
+
+    ret.truncate(n);
+    ret
+}
+
+#[test]
+fn poly_inv_test() {
+    type GF = crate::gf::GF<998244353>;
+    let a = (1..100).map(|i| GF::new(i * i + 1)).collect::<Vec<_>>();
+    let b = poly_inv(&a, 150);
+    let c = convolution(&a, &b);
+    assert_eq!(c[0], GF::new(1));
+    assert!(c[1..150].iter().all(|&x| x == GF::new(0)));
+}
+
+/// O(n log n).
+/// Division with remainder of polynomials: returns `(q, r)` such that `a = b q + r` and `deg r < deg b`.
+///
+/// The last element of `b` (the leading coefficient) must not be zero.
+/// `r` always has `b.len() - 1` coefficients.
+pub fn poly_div_rem<const P: u64>(a: &[GF<P>], b: &[GF<P>]) -> (Vec<GF<P>>, Vec<GF<P>>) {
+    assert!(b.last().is_some_and(|x| x.0 != 0));
+
+    let m = b.len();
+    if a.len() < m {
+        let mut r = a.to_vec();
+        r.resize(m - 1, GF::new(0));
+        return (vec![], r);
+    }
+
+    let k = a.len() - m + 1;
+    let q = if k.min(m) <= 32 {
+        let inv = b[m - 1].recip();
+        let mut a = a.to_vec();
+        let mut q = vec![GF::new(0); k];
+        for i in (0..k).rev() {
+            let c = a[i + m - 1] * inv;
+            q[i] = c;
+            for (j, &y) in b.iter().enumerate() {
+                a[i + j] -= c * y;
+            }
+        }
+        q
+    } else {
+        let ra = a.iter().rev().take(k).cloned().collect::<Vec<_>>();
+        let rb = b.iter().rev().cloned().collect::<Vec<_>>();
+        let mut q = convolution(&ra, &poly_inv(&rb, k));
+        q.truncate(k);
+        q.reverse();
+        q
+    };
+
+    let bq = convolution(b, &q);
+    let r = a[..m - 1]
+        .iter()
+        .zip(bq.iter())
+        .map(|(&x, &y)| x - y)
+        .collect();
+
+    (q, r)
+}
+
+#[test]
+fn poly_div_rem_test() {
+    type GF = crate::gf::GF<998244353>;
+
+    for &(n, m) in &[(10, 3), (200, 50), (200, 150), (3, 10), (100, 1)] {
+        let a = (0..n).map(|i| GF::new(i * 7 + 3)).collect::<Vec<_>>();
+        let b = (0..m).map(|i| GF::new(i * i + 1)).collect::<Vec<_>>();
+        let (q, r) = poly_div_rem(&a, &b);
+        assert_eq!(r.len(), m as usize - 1);
+
+        let mut c = convolution(&b, &q);
+        c.resize(n.max(m) as usize, GF::new(0));
+        for (x, &y) in c.iter_mut().zip(r.iter()) {
+            *x += y;
+        }
+        c.truncate(n as usize);
+        assert_eq!(c, a);
+    }
+}
+
+/// O(n).
+/// Derivative of polynomial `a`
+pub fn poly_derivative<const P: u64>(a: &[GF<P>]) -> Vec<GF<P>> {
+    a.iter().enumerate().skip(1).map(|(i, &x)| x * i).collect()
+}
+
+/// O(n).
+/// Evaluate polynomial `a` at `x` (Horner's method)
+pub fn poly_eval<const P: u64>(a: &[GF<P>], x: GF<P>) -> GF<P> {
+    a.iter().rev().fold(GF::new(0), |acc, &c| acc * x + c)
+}
@@ -26,6 +26,7 @@ pub use crate::collections::*;
 pub use crate::display::*;
 pub use crate::gf::*;
 pub use crate::inf::{MaybeInf::*, *};
+pub use crate::integer::*;
 pub use crate::iter::*;
 pub use crate::ix::*;
 pub use crate::monoid::*;
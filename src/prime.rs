@@ -1,5 +1,7 @@
+use crate::integer::isqrt;
+use crate::number::{mul_mod_u64, pow_mod_u64};
 use num::{pow, One};
-use std::ops::{Div, MulAssign, Sub};
+use std::ops::{Add, Div, Mul, MulAssign, Sub};
 
 /// Make bool table by using Sieve of Eratosthenes
 pub fn sieve(n: usize) -> Vec<bool> {
@@ -29,21 +31,11 @@ fn sieve_test() {
 
 /// Returns all primes less than `n`
 pub fn primes(n: usize) -> Vec<usize> {
-    let mut tbl = vec![true; n];
-    let mut ps = vec![];
-    tbl[0] = false;
-    tbl[1] = false;
-    for i in 2..n {
-        if tbl[i] {
-            ps.push(i);
-            let mut j = i + i;
-            while j < n {
-                tbl[j] = false;
-                j += i;
-            }
-        }
-    }
-    ps
+    sieve(n)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, b)| if b { Some(i) } else { None })
+        .collect()
 }
 
 #[test]
@@ -55,7 +47,7 @@ fn primes_test() {
 pub fn factor(n: usize) -> Vec<(usize, usize)> {
     let mut n = n;
     let mut ret = vec![];
-    let ps = primes((n as f64).sqrt() as usize + 2);
+    let ps = primes((isqrt(n as u64) as usize + 1).max(2));
     for p in ps {
         let mut cnt = 0;
         while n % p == 0 {
@@ -74,6 +66,7 @@ pub fn factor(n: usize) -> Vec<(usize, usize)> {
 
 #[test]
 fn factor_test() {
+    assert_eq!(factor(0), &[]);
     assert_eq!(factor(1), &[]);
     assert_eq!(factor(2), &[(2, 1)]);
     assert_eq!(factor(4), &[(2, 2)]);
@@ -124,9 +117,34 @@ fn sum_of_divisors_test() {
 
 /// Returns all divisors of `n`
 pub fn divisors(n: usize) -> Vec<usize> {
-    let fs = factor(n);
+    divisors_from_factors(&factor(n))
+}
+
+/// O(sqrt n).
+/// Returns all divisors of `n` in increasing order
+pub fn sorted_divisors(n: usize) -> Vec<usize> {
+    let mut ret = divisors(n);
+    ret.sort_unstable();
+    ret
+}
+
+#[test]
+fn sorted_divisors_test() {
+    assert_eq!(sorted_divisors(1), vec![1]);
+    assert_eq!(
+        sorted_divisors(60),
+        vec![1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60]
+    );
+    for n in 1..300 {
+        let expected = (1..=n).filter(|&d| n % d == 0).collect::<Vec<_>>();
+        assert_eq!(sorted_divisors(n), expected);
+    }
+}
+
+/// Returns all divisors of the number whose prime factorization is `factors`
+pub fn divisors_from_factors(factors: &[(usize, usize)]) -> Vec<usize> {
     let mut ret = vec![];
-    gen_divisors(&fs[..], 1, &mut ret);
+    gen_divisors(factors, 1, &mut ret);
     ret
 }
 
@@ -142,3 +160,771 @@ fn gen_divisors(s: &[(usize, usize)], cur: usize, ret: &mut Vec<usize>) {
         cur = cur.wrapping_mul(s[0].0);
     }
 }
+
+/// Linear sieve with the smallest prime factor table
+///
+/// Construction takes O(n), and factorization of each number takes O(log x).
+///
+/// ```
+/// # use competitive::prime::LinearSieve;
+/// let ls = LinearSieve::new(100);
+/// assert!(ls.is_prime(97));
+/// assert_eq!(ls.factor(60), vec![(2, 2), (3, 1), (5, 1)]);
+/// assert_eq!(ls.divisors(12), vec![1, 2, 3, 4, 6, 12]);
+/// ```
+pub struct LinearSieve {
+    lpf: Vec<usize>,
+    primes: Vec<usize>,
+}
+
+impl LinearSieve {
+    /// O(n).
+    /// Construct sieve for numbers less than `n`
+    pub fn new(n: usize) -> Self {
+        let mut lpf = vec![0; n];
+        let mut primes = vec![];
+        for i in 2..n {
+            if lpf[i] == 0 {
+                lpf[i] = i;
+                primes.push(i);
+            }
+            for &p in primes.iter() {
+                if p > lpf[i] || i * p >= n {
+                    break;
+                }
+                lpf[i * p] = p;
+            }
+        }
+        Self { lpf, primes }
+    }
+
+    /// O(1).
+    /// Upper bound (exclusive) of the numbers the sieve covers
+    pub fn len(&self) -> usize {
+        self.lpf.len()
+    }
+
+    /// O(1).
+    /// Whether the sieve covers no numbers
+    pub fn is_empty(&self) -> bool {
+        self.lpf.is_empty()
+    }
+
+    /// O(1).
+    pub fn is_prime(&self, x: usize) -> bool {
+        x >= 2 && self.lpf[x] == x
+    }
+
+    /// O(1).
+    /// All primes less than `self.len()` in increasing order
+    pub fn primes(&self) -> &[usize] {
+        &self.primes
+    }
+
+    /// O(1).
+    /// Smallest prime factor of `x` (`x >= 2`)
+    pub fn smallest_prime_factor(&self, x: usize) -> usize {
+        assert!(x >= 2);
+        self.lpf[x]
+    }
+
+    /// O(log x).
+    /// Prime factorization in the same format as `factor`
+    pub fn factor(&self, x: usize) -> Vec<(usize, usize)> {
+        assert!(x >= 1);
+        let mut x = x;
+        let mut ret: Vec<(usize, usize)> = vec![];
+        while x > 1 {
+            let p = self.lpf[x];
+            match ret.last_mut() {
+                Some(r) if r.0 == p => r.1 += 1,
+                _ => ret.push((p, 1)),
+            }
+            x /= p;
+        }
+        ret
+    }
+
+    /// O(d(x) log d(x)).
+    /// All divisors of `x` in increasing order
+    pub fn divisors(&self, x: usize) -> Vec<usize> {
+        let fs = self.factor(x);
+        let mut ret = vec![];
+        gen_divisors(&fs, 1, &mut ret);
+        ret.sort_unstable();
+        ret
+    }
+
+    /// O(n).
+    /// Table of Euler's totient function `phi(i)` for `i < self.len()`
+    pub fn euler_phi_table(&self) -> Vec<usize> {
+        let n = self.len();
+        let mut phi = vec![0; n];
+        if n > 1 {
+            phi[1] = 1;
+        }
+        for i in 2..n {
+            let p = self.lpf[i];
+            let j = i / p;
+            phi[i] = if self.lpf[j] == p {
+                phi[j] * p
+            } else {
+                phi[j] * (p - 1)
+            };
+        }
+        phi
+    }
+
+    /// O(n).
+    /// Table of Mobius function `mu(i)` for `i < self.len()`
+    pub fn mobius_table(&self) -> Vec<i64> {
+        let n = self.len();
+        let mut mu = vec![0; n];
+        if n > 1 {
+            mu[1] = 1;
+        }
+        for i in 2..n {
+            let p = self.lpf[i];
+            let j = i / p;
+            mu[i] = if self.lpf[j] == p { 0 } else { -mu[j] };
+        }
+        mu
+    }
+}
+
+#[test]
+fn linear_sieve_test() {
+    let n = 1000;
+    let ls = LinearSieve::new(n);
+    let tbl = sieve(n);
+
+    assert_eq!(ls.primes(), &primes(n)[..]);
+    for (x, &b) in tbl.iter().enumerate() {
+        assert_eq!(ls.is_prime(x), b);
+    }
+    for x in 1..n {
+        assert_eq!(ls.factor(x), factor(x));
+        let mut ds = divisors(x);
+        ds.sort_unstable();
+        assert_eq!(ls.divisors(x), ds);
+    }
+
+    let phi = ls.euler_phi_table();
+    let mu = ls.mobius_table();
+    for x in 1..n {
+        assert_eq!(
+            phi[x],
+            (1..=x).filter(|&y| num::integer::gcd(x, y) == 1).count()
+        );
+        let fs = factor(x);
+        let expected = if fs.iter().any(|r| r.1 > 1) {
+            0
+        } else if fs.len().is_multiple_of(2) {
+            1
+        } else {
+            -1
+        };
+        assert_eq!(mu[x], expected);
+    }
+
+    assert!(LinearSieve::new(0).is_empty());
+    assert_eq!(LinearSieve::new(2).euler_phi_table(), vec![0, 1]);
+}
+
+/// O(log n).
+/// Deterministic Miller-Rabin primality test for all `u64`
+pub fn is_prime_u64(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &p in &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    // This base set is known to be sufficient for n < 2^64
+    for &a in &[2, 325, 9375, 28178, 450775, 9780504, 1795265022] {
+        let a = a % n;
+        if a == 0 {
+            continue;
+        }
+        let mut x = pow_mod_u64(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut composite = true;
+        for _ in 1..s {
+            x = mul_mod_u64(x, x, n);
+            if x == n - 1 {
+                composite = false;
+                break;
+            }
+        }
+        if composite {
+            return false;
+        }
+    }
+    true
+}
+
+#[test]
+fn is_prime_u64_test() {
+    let tbl = sieve(100000);
+    for (i, &b) in tbl.iter().enumerate() {
+        assert_eq!(is_prime_u64(i as u64), b);
+    }
+
+    // strong pseudoprimes to several small bases
+    assert!(!is_prime_u64(3215031751));
+    assert!(!is_prime_u64(3825123056546413051));
+    assert!(is_prime_u64(1_000_000_007));
+    assert!(is_prime_u64(1_000_000_000_000_000_003));
+    assert!(is_prime_u64((1 << 61) - 1));
+    assert!(is_prime_u64(18446744073709551557));
+    assert!(!is_prime_u64(u64::MAX));
+}
+
+/// Find a non-trivial divisor of odd composite `n` by Pollard's rho algorithm with Brent's cycle detection
+fn pollard_rho(n: u64) -> u64 {
+    const M: u64 = 128;
+
+    for c in 1.. {
+        let f = |x: u64| ((x as u128 * x as u128 + c as u128) % n as u128) as u64;
+
+        let mut y = 2;
+        let mut x = y;
+        let mut ys = y;
+        let mut g = 1;
+        let mut q = 1;
+        let mut r = 1;
+
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..M.min(r - k) {
+                    y = f(y);
+                    q = mul_mod_u64(q, x.abs_diff(y), n);
+                }
+                g = num::integer::gcd(q, n);
+                k += M;
+            }
+            r *= 2;
+        }
+
+        if g == n {
+            loop {
+                ys = f(ys);
+                g = num::integer::gcd(x.abs_diff(ys), n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
+
+fn factor_u64_rec(n: u64, ret: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime_u64(n) {
+        ret.push(n);
+        return;
+    }
+    let d = pollard_rho(n);
+    factor_u64_rec(d, ret);
+    factor_u64_rec(n / d, ret);
+}
+
+/// O(n^(1/4) log n) expected.
+/// Prime factorization for all `u64` by Pollard's rho algorithm.
+///
+/// The result is sorted and in the same format as `factor`,
+/// so it can be passed to `num_of_divisors`, `sum_of_divisors` and `divisors_from_factors`.
+pub fn factor_u64(n: u64) -> Vec<(usize, usize)> {
+    assert!(n >= 1);
+    let mut n = n;
+    let mut ps = vec![];
+    for p in 2..100 {
+        while n.is_multiple_of(p) {
+            ps.push(p);
+            n /= p;
+        }
+    }
+    factor_u64_rec(n, &mut ps);
+    ps.sort_unstable();
+
+    let mut ret: Vec<(usize, usize)> = vec![];
+    for p in ps {
+        match ret.last_mut() {
+            Some(r) if r.0 == p as usize => r.1 += 1,
+            _ => ret.push((p as usize, 1)),
+        }
+    }
+    ret
+}
+
+#[test]
+fn factor_u64_test() {
+    for n in 1..3000 {
+        assert_eq!(factor_u64(n as u64), factor(n));
+    }
+
+    for &n in &[
+        999999937 * 999999929,
+        1_000_000_007 * 1_000_000_007,
+        4611686018427387847 * 3,
+        18446744073709551557,
+        u64::MAX,
+        1 << 63,
+        600851475143,
+    ] {
+        let fs = factor_u64(n);
+        let mut m = 1_u64;
+        for w in fs.windows(2) {
+            assert!(w[0].0 < w[1].0);
+        }
+        for &(p, e) in fs.iter() {
+            assert!(is_prime_u64(p as u64));
+            m *= (p as u64).pow(e as u32);
+        }
+        assert_eq!(m, n);
+    }
+
+    assert_eq!(num_of_divisors(&factor_u64(1 << 63)), 64);
+    assert_eq!(
+        divisors_from_factors(&factor_u64(999999937 * 999999929)).len(),
+        4
+    );
+}
+
+/// O((r - l) log log r + sqrt r).
+/// Make bool table of primality for numbers in `l..r` by segmented sieve.
+/// `ret[i]` is true iff `l + i` is prime.
+pub fn segmented_sieve(l: u64, r: u64) -> Vec<bool> {
+    assert!(l <= r);
+    let mut tbl = vec![true; (r - l) as usize];
+    for i in l..r.min(2) {
+        tbl[(i - l) as usize] = false;
+    }
+    if r <= 2 {
+        return tbl;
+    }
+
+    for p in primes(isqrt(r - 1) as usize + 1) {
+        let p = p as u64;
+        let mut j = (p * p).max(l.div_ceil(p) * p);
+        while j < r {
+            tbl[(j - l) as usize] = false;
+            j += p;
+        }
+    }
+    tbl
+}
+
+#[test]
+fn segmented_sieve_test() {
+    let tbl = sieve(2000);
+    for l in 0..50 {
+        for r in l..300 {
+            assert_eq!(segmented_sieve(l, r), &tbl[l as usize..r as usize]);
+        }
+    }
+
+    let l = 1_000_000_000_000;
+    let tbl = segmented_sieve(l, l + 1000);
+    for (i, &b) in tbl.iter().enumerate() {
+        assert_eq!(b, is_prime_u64(l + i as u64));
+    }
+}
+
+/// O((r - l) log r + sqrt r).
+/// Prime factorization of every number in `l..r` by dividing out small primes.
+/// `ret[i]` is the factorization of `l + i` in the same format as `factor` (empty for `0` and `1`).
+pub fn segmented_factor(l: u64, r: u64) -> Vec<Vec<(usize, usize)>> {
+    assert!(l <= r);
+    let mut rest = (l..r).collect::<Vec<_>>();
+    let mut ret = vec![vec![]; (r - l) as usize];
+    if r <= 2 {
+        return ret;
+    }
+
+    for p in primes(isqrt(r - 1) as usize + 1) {
+        let p = p as u64;
+        let mut j = l.div_ceil(p).max(1) * p;
+        while j < r {
+            let i = (j - l) as usize;
+            let mut e = 0;
+            while rest[i] % p == 0 {
+                rest[i] /= p;
+                e += 1;
+            }
+            ret[i].push((p as usize, e));
+            j += p;
+        }
+    }
+
+    for (fs, x) in ret.iter_mut().zip(rest) {
+        if x > 1 {
+            fs.push((x as usize, 1));
+        }
+    }
+    ret
+}
+
+#[test]
+fn segmented_factor_test() {
+    let fs = segmented_factor(1, 1000);
+    for (i, f) in fs.iter().enumerate() {
+        assert_eq!(f, &factor(i + 1));
+    }
+
+    let l = 1_000_000_000_000;
+    for (i, f) in segmented_factor(l, l + 500).into_iter().enumerate() {
+        assert_eq!(f, factor_u64(l + i as u64));
+    }
+
+    assert_eq!(segmented_factor(0, 2), vec![vec![], vec![]]);
+    assert_eq!(segmented_factor(0, 5)[4], vec![(2, 2)]);
+}
+
+/// Table of `sum_{p <= v, p prime} f(p)` for every `v` of the form `n / k`,
+/// computed by Lucy_Hedgehog's algorithm.
+pub struct PrimeSumTable<T> {
+    n: u64,
+    small: Vec<T>,
+    large: Vec<T>,
+}
+
+impl<T> PrimeSumTable<T>
+where
+    T: Clone + Sub<Output = T> + Mul<Output = T>,
+{
+    /// O(n^(3/4) / log n) time, O(sqrt n) memory.
+    ///
+    /// * `f` - completely multiplicative function (e.g. `1`, `x`, `x^2`)
+    /// * `sum_f` - `sum_f(v) = f(2) + f(3) + ... + f(v)` for `v >= 1`
+    pub fn new(n: u64, f: impl Fn(u64) -> T, sum_f: impl Fn(u64) -> T) -> Self {
+        let sq = isqrt(n);
+        let lk = (n / (sq + 1)) as usize;
+        let mut small = (0..=sq).map(|v| sum_f(v.max(1))).collect::<Vec<_>>();
+        let mut large = (0..=lk as u64)
+            .map(|k| sum_f(n.checked_div(k).unwrap_or(1)))
+            .collect::<Vec<_>>();
+
+        for p in primes((sq as usize + 1).max(2)) {
+            let p = p as u64;
+            let fp = f(p);
+            let sp = small[p as usize - 1].clone();
+
+            // v = n / k in decreasing order while v >= p^2
+            let kmax = (n / (p * p)).min(lk as u64) as usize;
+            for k in 1..=kmax {
+                let kp = k as u64 * p;
+                let s = if kp <= lk as u64 {
+                    large[kp as usize].clone()
+                } else {
+                    small[(n / kp) as usize].clone()
+                };
+                large[k] = large[k].clone() - fp.clone() * (s - sp.clone());
+            }
+            for v in (p * p..=sq).rev() {
+                let s = small[(v / p) as usize].clone();
+                small[v as usize] = small[v as usize].clone() - fp.clone() * (s - sp.clone());
+            }
+        }
+        Self { n, small, large }
+    }
+
+    /// O(1).
+    /// Returns `sum_{p <= v} f(p)`. `v` must be of the form `n / k`.
+    pub fn get(&self, v: u64) -> &T {
+        if v < self.small.len() as u64 {
+            &self.small[v as usize]
+        } else {
+            let k = self.n / v;
+            assert_eq!(self.n / k, v);
+            &self.large[k as usize]
+        }
+    }
+}
+
+/// O(n^(3/4) / log n) time, O(sqrt n) memory.
+/// Number of primes less than or equal to `n`.
+pub fn prime_count(n: u64) -> u64 {
+    *PrimeSumTable::new(n, |_| 1, |v| v - 1).get(n)
+}
+
+/// O(n^(3/4) / log n) time, O(sqrt n) memory.
+/// Sum of primes less than or equal to `n`.
+///
+/// The result exceeds `u64` for `n` around `1e10`. Use `u128` or `GF<P>` for larger `n`.
+pub fn prime_sum<T>(n: u64) -> T
+where
+    T: Clone + Sub<Output = T> + Mul<Output = T> + From<u64>,
+{
+    let table = PrimeSumTable::new(
+        n,
+        |p| T::from(p),
+        |v| {
+            // v (v + 1) / 2 - 1 without overflow
+            let s = if v % 2 == 0 {
+                T::from(v / 2) * T::from(v + 1)
+            } else {
+                T::from(v) * T::from(v.div_ceil(2))
+            };
+            s - T::from(1)
+        },
+    );
+    table.get(n).clone()
+}
+
+#[test]
+fn prime_sum_table_test() {
+    type GF = crate::gf::GF<1000000007>;
+
+    let tbl = sieve(3000);
+    let mut cnt = 0;
+    let mut sum = 0;
+    for (n, &is_p) in tbl.iter().enumerate() {
+        if is_p {
+            cnt += 1;
+            sum += n as u64;
+        }
+        assert_eq!(prime_count(n as u64), cnt);
+        assert_eq!(prime_sum::<u64>(n as u64), sum);
+        assert_eq!(prime_sum::<GF>(n as u64), GF::new(sum));
+    }
+
+    // sum of squares of primes
+    let n = 2000;
+    let table = PrimeSumTable::new(n, |p| p * p, |v| v * (v + 1) * (2 * v + 1) / 6 - 1);
+    for k in 1..=n {
+        let v = n / k;
+        let expected = (0..=v)
+            .filter(|&p| tbl[p as usize])
+            .map(|p| p * p)
+            .sum::<u64>();
+        assert_eq!(*table.get(v), expected);
+    }
+
+    assert_eq!(prime_count(1_000_000_000), 50847534);
+    assert_eq!(prime_sum::<u128>(1_000_000_000), 24739512092254535);
+    assert_eq!(prime_count(10_000_000_000), 455052511);
+}
+
+/// Divisors of `n` in increasing order, with zeta/Mobius transforms over the divisor lattice.
+///
+/// Functions on the lattice are slices indexed by the position of each divisor in `divisors()`.
+///
+/// ```
+/// # use competitive::prime::DivisorLattice;
+/// let dl = DivisorLattice::new(12);
+/// assert_eq!(dl.divisors(), &[1, 2, 3, 4, 6, 12]);
+/// assert_eq!(dl.index(4), Some(3));
+/// assert_eq!(dl.index(5), None);
+///
+/// let mut f = vec![1; dl.len()];
+/// dl.zeta_divisors(&mut f);
+/// assert_eq!(f, vec![1, 2, 2, 3, 4, 6]);
+/// ```
+pub struct DivisorLattice {
+    divs: Vec<usize>,
+    // steps[i]: pairs of positions of (d, d * p_i) for each prime factor p_i
+    steps: Vec<Vec<(usize, usize)>>,
+}
+
+impl DivisorLattice {
+    /// O(sqrt n + d(n) k log d(n)), where `k` is the number of distinct prime factors.
+    pub fn new(n: usize) -> Self {
+        Self::from_factors(&factor(n))
+    }
+
+    /// O(d(n) k log d(n)).
+    /// Construct from the prime factorization in the same format as `factor`.
+    pub fn from_factors(factors: &[(usize, usize)]) -> Self {
+        let mut divs = divisors_from_factors(factors);
+        divs.sort_unstable();
+        let mut ret = Self {
+            divs,
+            steps: vec![],
+        };
+
+        let n = ret.divs.last().cloned().unwrap_or(1);
+        let steps = factors
+            .iter()
+            .map(|&(p, _)| {
+                ret.divs
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &d)| (n / d).is_multiple_of(p))
+                    .map(|(i, &d)| (i, ret.index(d * p).unwrap()))
+                    .collect()
+            })
+            .collect();
+        ret.steps = steps;
+        ret
+    }
+
+    /// Number of divisors
+    pub fn len(&self) -> usize {
+        self.divs.len()
+    }
+
+    /// Always false, since `1` divides any number
+    pub fn is_empty(&self) -> bool {
+        self.divs.is_empty()
+    }
+
+    /// All divisors in increasing order
+    pub fn divisors(&self) -> &[usize] {
+        &self.divs
+    }
+
+    /// O(log d(n)).
+    /// Position of `d` in `divisors()`, or `None` if `d` does not divide `n`.
+    pub fn index(&self, d: usize) -> Option<usize> {
+        self.divs.binary_search(&d).ok()
+    }
+
+    /// O(d(n) k).
+    /// Replace `f(m)` with `sum_{d | m} f(d)`.
+    pub fn zeta_divisors<T: Clone + Add<Output = T>>(&self, f: &mut [T]) {
+        assert_eq!(f.len(), self.len());
+        for step in &self.steps {
+            for &(i, j) in step {
+                f[j] = f[j].clone() + f[i].clone();
+            }
+        }
+    }
+
+    /// O(d(n) k).
+    /// Inverse of `zeta_divisors`.
+    pub fn mobius_divisors<T: Clone + Sub<Output = T>>(&self, f: &mut [T]) {
+        assert_eq!(f.len(), self.len());
+        for step in &self.steps {
+            for &(i, j) in step.iter().rev() {
+                f[j] = f[j].clone() - f[i].clone();
+            }
+        }
+    }
+
+    /// O(d(n) k).
+    /// Replace `f(d)` with `sum_{d | m | n} f(m)`.
+    pub fn zeta_multiples<T: Clone + Add<Output = T>>(&self, f: &mut [T]) {
+        assert_eq!(f.len(), self.len());
+        for step in &self.steps {
+            for &(i, j) in step.iter().rev() {
+                f[i] = f[i].clone() + f[j].clone();
+            }
+        }
+    }
+
+    /// O(d(n) k).
+    /// Inverse of `zeta_multiples`.
+    pub fn mobius_multiples<T: Clone + Sub<Output = T>>(&self, f: &mut [T]) {
+        assert_eq!(f.len(), self.len());
+        for step in &self.steps {
+            for &(i, j) in step {
+                f[i] = f[i].clone() - f[j].clone();
+            }
+        }
+    }
+
+    /// O(d(n) k).
+    /// `c(d) = sum_{gcd(a, b) = d} f(a) g(b)` over divisors `a`, `b` of `n`.
+    pub fn gcd_convolution<T>(&self, f: &[T], g: &[T]) -> Vec<T>
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let mut f = f.to_vec();
+        let mut g = g.to_vec();
+        self.zeta_multiples(&mut f);
+        self.zeta_multiples(&mut g);
+        let mut ret = f.into_iter().zip(g).map(|(a, b)| a * b).collect::<Vec<_>>();
+        self.mobius_multiples(&mut ret);
+        ret
+    }
+
+    /// O(d(n) k).
+    /// `c(d) = sum_{lcm(a, b) = d} f(a) g(b)` over divisors `a`, `b` of `n`.
+    pub fn lcm_convolution<T>(&self, f: &[T], g: &[T]) -> Vec<T>
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let mut f = f.to_vec();
+        let mut g = g.to_vec();
+        self.zeta_divisors(&mut f);
+        self.zeta_divisors(&mut g);
+        let mut ret = f.into_iter().zip(g).map(|(a, b)| a * b).collect::<Vec<_>>();
+        self.mobius_divisors(&mut ret);
+        ret
+    }
+}
+
+#[test]
+fn divisor_lattice_test() {
+    use num::Integer;
+
+    for n in 1..200 {
+        let dl = DivisorLattice::new(n);
+        let ds = sorted_divisors(n);
+        assert_eq!(dl.divisors(), &ds[..]);
+        for (i, &d) in ds.iter().enumerate() {
+            assert_eq!(dl.index(d), Some(i));
+        }
+
+        let f = ds
+            .iter()
+            .map(|&d| (d * d % 7) as i64 - 3)
+            .collect::<Vec<_>>();
+        let g = ds.iter().map(|&d| (d % 5) as i64 + 1).collect::<Vec<_>>();
+
+        let mut zd = f.clone();
+        dl.zeta_divisors(&mut zd);
+        let mut zm = f.clone();
+        dl.zeta_multiples(&mut zm);
+        for (i, &m) in ds.iter().enumerate() {
+            let sd = ds.iter().zip(&f).filter(|&(&d, _)| m % d == 0);
+            assert_eq!(zd[i], sd.map(|(_, &x)| x).sum::<i64>());
+            let sm = ds.iter().zip(&f).filter(|&(&d, _)| d % m == 0);
+            assert_eq!(zm[i], sm.map(|(_, &x)| x).sum::<i64>());
+        }
+        dl.mobius_divisors(&mut zd);
+        assert_eq!(zd, f);
+        dl.mobius_multiples(&mut zm);
+        assert_eq!(zm, f);
+
+        let mut gcd = vec![0; ds.len()];
+        let mut lcm = vec![0; ds.len()];
+        for (&a, &x) in ds.iter().zip(&f) {
+            for (&b, &y) in ds.iter().zip(&g) {
+                gcd[dl.index(a.gcd(&b)).unwrap()] += x * y;
+                lcm[dl.index(a.lcm(&b)).unwrap()] += x * y;
+            }
+        }
+        assert_eq!(dl.gcd_convolution(&f, &g), gcd);
+        assert_eq!(dl.lcm_convolution(&f, &g), lcm);
+    }
+
+    // count pairs of divisors of 720720 whose gcd is d
+    let dl = DivisorLattice::new(720720);
+    let one = vec![1_u64; dl.len()];
+    let cnt = dl.gcd_convolution(&one, &one);
+    assert_eq!(cnt.iter().sum::<u64>(), (dl.len() * dl.len()) as u64);
+    assert_eq!(cnt[dl.len() - 1], 1);
+}
@@ -0,0 +1,162 @@
+use crate::graph::scc::strongly_connected_components;
+
+/// 2-SAT solver
+///
+/// Literal `(i, f)` means that the variable `x_i` is `f`.
+///
+/// ```
+/// # use competitive::two_sat::TwoSat;
+/// let mut ts = TwoSat::new(3);
+/// ts.add_clause(0, false, 1, true);
+/// ts.implies(0, true, 2, false);
+/// ts.exactly_one(1, true, 2, true);
+/// ts.set(2, true);
+/// assert_eq!(ts.solve(), Some(vec![false, false, true]));
+///
+/// ts.set(0, true);
+/// assert_eq!(ts.solve(), None);
+/// ```
+pub struct TwoSat {
+    n: usize,
+    g: Vec<Vec<usize>>,
+}
+
+impl TwoSat {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            g: vec![vec![]; 2 * n],
+        }
+    }
+
+    /// Number of variables including auxiliary ones
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Add a new variable and return its index.
+    pub fn new_var(&mut self) -> usize {
+        self.g.push(vec![]);
+        self.g.push(vec![]);
+        self.n += 1;
+        self.n - 1
+    }
+
+    fn node(&self, i: usize, f: bool) -> usize {
+        assert!(i < self.n);
+        2 * i + f as usize
+    }
+
+    /// Add clause `(x_i = f) or (x_j = g)`.
+    pub fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        let (a, na) = (self.node(i, f), self.node(i, !f));
+        let (b, nb) = (self.node(j, g), self.node(j, !g));
+        self.g[na].push(b);
+        self.g[nb].push(a);
+    }
+
+    /// Add clause `(x_i = f) implies (x_j = g)`.
+    pub fn implies(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        self.add_clause(i, !f, j, g);
+    }
+
+    /// Add clause `x_i = f`.
+    pub fn set(&mut self, i: usize, f: bool) {
+        self.add_clause(i, f, i, f);
+    }
+
+    /// Add clause that exactly one of `(x_i = f)` and `(x_j = g)` holds.
+    pub fn exactly_one(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        self.add_clause(i, f, j, g);
+        self.add_clause(i, !f, j, !g);
+    }
+
+    /// O(k).
+    /// Add clauses that at most one of literals `lits` holds, using `k - 1` auxiliary variables.
+    pub fn at_most_one(&mut self, lits: &[(usize, bool)]) {
+        // s_k: some of lits[0..=k] holds
+        let mut prev: Option<usize> = None;
+        for (k, &(i, f)) in lits.iter().enumerate() {
+            if let Some(s) = prev {
+                self.implies(s, true, i, !f);
+            }
+            if k + 1 == lits.len() {
+                break;
+            }
+            let s = self.new_var();
+            self.implies(i, f, s, true);
+            if let Some(p) = prev {
+                self.implies(p, true, s, true);
+            }
+            prev = Some(s);
+        }
+    }
+
+    /// O(n + m).
+    /// Returns an assignment satisfying all clauses, or `None` if there is no such assignment.
+    /// The result contains auxiliary variables at the end.
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let scc = strongly_connected_components(&self.g);
+        (0..self.n)
+            .map(|i| {
+                let (t, f) = (scc.id[2 * i + 1], scc.id[2 * i]);
+                // choose the literal later in topological order
+                if t == f {
+                    None
+                } else {
+                    Some(t > f)
+                }
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn two_sat_test() {
+    // compare with brute force on random instances
+    for seed in 0..200_usize {
+        let n = 6;
+        let m = 3 + seed % 10;
+        let clauses = (0..m)
+            .map(|k| {
+                let x = (seed * 131 + k * 71) ^ (seed >> 2);
+                (x % n, x / n % 2 == 0, x / 17 % n, x / 7 % 2 == 0)
+            })
+            .collect::<Vec<_>>();
+
+        let mut ts = TwoSat::new(n);
+        for &(i, f, j, g) in &clauses {
+            ts.add_clause(i, f, j, g);
+        }
+        let sat = |a: &[bool]| clauses.iter().all(|&(i, f, j, g)| a[i] == f || a[j] == g);
+        let exists =
+            (0..1 << n).any(|s: usize| sat(&(0..n).map(|i| s >> i & 1 == 1).collect::<Vec<_>>()));
+
+        match ts.solve() {
+            Some(a) => assert!(sat(&a)),
+            None => assert!(!exists),
+        }
+        assert_eq!(ts.solve().is_some(), exists);
+    }
+
+    // at most one of 5 variables, and at least one of each pair
+    let mut ts = TwoSat::new(5);
+    ts.at_most_one(&(0..5).map(|i| (i, true)).collect::<Vec<_>>());
+    ts.add_clause(1, true, 3, true);
+    let a = ts.solve().unwrap();
+    assert_eq!(a[..5].iter().filter(|&&b| b).count(), 1);
+    assert!(a[1] || a[3]);
+
+    ts.add_clause(0, true, 2, true);
+    assert_eq!(ts.solve(), None);
+
+    let mut ts = TwoSat::new(4);
+    ts.at_most_one(&[(0, false), (1, true), (2, false), (3, true)]);
+    ts.set(0, false);
+    let a = ts.solve().unwrap();
+    assert_eq!(&a[..4], &[false, false, true, false]);
+}